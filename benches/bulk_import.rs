@@ -0,0 +1,51 @@
+//! Bulk import of a 30,000-printing catalog: `cargo bench --bench bulk_import`
+
+use std::time::{Duration, Instant};
+
+use mtg_multitool::collections::Collection;
+
+const PRINTINGS: usize = 30_000 ;
+
+/// Whole import budget, far above what indexed lookups need but far below what linear scans would take
+const BUDGET: Duration = Duration::from_secs(1) ;
+
+fn main()
+{
+    let lines: Vec<String> = (1..=PRINTINGS)
+        .map(|number| format!("BLK C {:05};Bulk card {};1;Sorcery;C;", number, number))
+        .collect() ;
+    let serials: Vec<String> = (1..=PRINTINGS).map(|number| format!("BLK C {}", number)).collect() ;
+    let names: Vec<String> = (1..=PRINTINGS).map(|number| format!("bulk CARD {}", number)).collect() ;
+
+    let start = Instant::now() ;
+    let mut collection = Collection::make(lines.iter().map(String::as_str).collect()).unwrap() ;
+    let built = start.elapsed() ;
+
+    let start = Instant::now() ;
+    for serial in &serials
+    {
+        collection.add(serial.as_str(), 4).unwrap() ;
+    }
+    let by_serial = start.elapsed() ;
+
+    let start = Instant::now() ;
+    for name in &names
+    {
+        collection.remove(name.as_str(), 1).unwrap() ;
+    }
+    let by_name = start.elapsed() ;
+
+    let start = Instant::now() ;
+    let total: usize = serials.iter().filter_map(|serial| collection.count(serial.as_str())).sum() ;
+    let counted = start.elapsed() ;
+
+    assert_eq!(total, 3 * PRINTINGS) ;
+
+    println!("catalog of {} printings built in {:?}", PRINTINGS, built) ;
+    println!("{} adds by serial in {:?}", PRINTINGS, by_serial) ;
+    println!("{} removes by name in {:?}", PRINTINGS, by_name) ;
+    println!("{} counts in {:?}", PRINTINGS, counted) ;
+
+    let elapsed = built + by_serial + by_name + counted ;
+    assert!(elapsed < BUDGET, "Bulk import took {:?}, over the {:?} budget", elapsed, BUDGET) ;
+}
@@ -48,7 +48,7 @@ fn ltr()
 
         code_lines.push(
             format!(
-                "pub const {}: &'static str = \"{}\" ;",
+                "pub const {}: &str = \"{}\" ;",
                 var_name.as_str(),
                 input_line.escape_default().collect::<String>().as_str(),
             )
@@ -70,7 +70,7 @@ fn ltr()
             ].join("")
         ) ;
     }
-    collection_code.push("\n\t])\n}".to_string()) ;
+    collection_code.push("\n\t]).expect(\"The set's csv file is not well formed\")\n}".to_string()) ;
 
     // Writing out code
 
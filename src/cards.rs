@@ -1,30 +1,32 @@
-use crate::{Classification, ManaCost, Rarity};
+use std::sync::Arc;
 
+use crate::{Classification, Frame, Language, ManaCost, Rarity};
+
+/// The abstract card (its "Oracle" text): what every printing of a card shares
 #[derive(Debug, Clone)]
-pub struct GenericCard<'a>
+pub struct OracleCard<'a>
 {
-    id: Option<&'a str>,
     name: &'a str,
 
     cost: ManaCost,
     kind: Classification,
-    rarity: Rarity,
 
     effects: &'a str,
 }
 
-impl<'s> GenericCard<'s>
+impl<'s> OracleCard<'s>
 {
-    pub fn id(&self) -> crate::ids::Id
+    /// Oracle cards are identified by their name
+    pub fn id(&self) -> crate::ids::Id<'s>
     {
-        use crate::ids::AsId ;
-        self.as_id()
+        crate::ids::Id::Name(self.name)
     }
 
     pub fn name(&self) -> &&'s str
     {
         &self.name
     }
+
     pub fn cost(&self) -> &ManaCost
     {
         &self.cost
@@ -35,17 +37,513 @@ impl<'s> GenericCard<'s>
         &self.kind
     }
 
+    pub fn effects(&self) -> &&'s str
+    {
+        &self.effects
+    }
+
+    /// Checks if both oracle cards are the same card, possibly printed in different sets
+    pub fn same_card(&self, other: &OracleCard<'_>) -> bool
+    {
+        crate::names::same_name(self.name, other.name)
+    }
+}
+
+/// One specific printing of an oracle card
+#[derive(Debug, Clone)]
+pub struct Printing<'a>
+{
+    /// Example: `"LTR C 0001"`
+    serial: Option<&'a str>,
+    rarity: Rarity,
+
+    artist: Option<&'a str>,
+    language: Language,
+    frame: Frame,
+}
+
+impl<'s> Printing<'s>
+{
+    pub fn serial(&self) -> Option<&'s str>
+    {
+        self.serial
+    }
+
+    /// Set code, such as `"LTR"`
+    pub fn set(&self) -> Option<String>
+    {
+        self.serial.and_then(crate::ids::parse_serial).map(|(set, _, _)| set)
+    }
+
+    /// Collector number within the set
+    pub fn number(&self) -> Option<usize>
+    {
+        self.serial.and_then(crate::ids::parse_serial).map(|(_, _, number)| number)
+    }
+
     pub fn rarity(&self) -> &Rarity
     {
         &self.rarity
     }
 
+    pub fn artist(&self) -> Option<&'s str>
+    {
+        self.artist
+    }
+
+    pub fn language(&self) -> &Language
+    {
+        &self.language
+    }
+
+    pub fn frame(&self) -> &Frame
+    {
+        &self.frame
+    }
+}
+
+/// A card as listed in a set: an oracle card along with one of its printings<br/>
+/// The oracle card is shared, not copied: within a [`crate::collections::Collection`],
+/// every printing of a card refers to the same oracle record
+#[derive(Debug, Clone)]
+pub struct GenericCard<'a>
+{
+    oracle: Arc<OracleCard<'a>>,
+    printing: Printing<'a>,
+}
+
+impl<'s> GenericCard<'s>
+{
+    pub fn id(&self) -> crate::ids::Id<'_>
+    {
+        use crate::ids::AsId ;
+        self.as_id()
+    }
+
+    pub fn oracle(&self) -> &OracleCard<'s>
+    {
+        &self.oracle
+    }
+
+    pub fn printing(&self) -> &Printing<'s>
+    {
+        &self.printing
+    }
+
+    pub fn name(&self) -> &&'s str
+    {
+        &self.oracle.name
+    }
+    pub fn cost(&self) -> &ManaCost
+    {
+        &self.oracle.cost
+    }
+
+    pub fn kind(&self) -> &Classification
+    {
+        &self.oracle.kind
+    }
+
+    pub fn rarity(&self) -> &Rarity
+    {
+        &self.printing.rarity
+    }
+
     pub fn effects(&self) -> &&'s str
     {
-        &self.effects
+        &self.oracle.effects
+    }
+
+    /// Checks if both cards are the very same printing of the same card
+    pub fn same_printing(&self, other: &GenericCard<'_>) -> bool
+    {
+        self.oracle.same_card(&other.oracle) && self.printing.serial == other.printing.serial
+    }
+
+    /// Checks if both cards refer to the very same oracle record, as printings of a collection do
+    pub fn shares_oracle(&self, other: &GenericCard<'s>) -> bool
+    {
+        Arc::ptr_eq(&self.oracle, &other.oracle)
+    }
+
+    /// Refers to the oracle record of another printing of the same card, instead of its own copy
+    pub(crate) fn share_oracle(&mut self, other: &GenericCard<'s>)
+    {
+        if self.oracle.same_card(&other.oracle)
+        {
+            self.oracle = Arc::clone(&other.oracle) ;
+        }
+    }
+}
+
+impl<'s> GenericCard<'s>
+{
+    /// Starts building a card in code, instead of parsing a csv line
+    /// ```
+    /// use mtg_multitool::{Classification, ManaCost, Rarity};
+    /// use mtg_multitool::cards::GenericCard;
+    ///
+    /// let card = GenericCard::builder()
+    ///     .serial("LTR C 0001")
+    ///     .name("Banish from Edoras")
+    ///     .cost(ManaCost::try_from("4 w").unwrap())
+    ///     .kind(Classification::Sorcery)
+    ///     .rarity(Rarity::Common)
+    ///     .effects("Exile target creature.")
+    ///     .build()
+    ///     .unwrap() ;
+    ///
+    /// assert_eq!(*card.name(), "Banish from Edoras") ;
+    /// ```
+    pub fn builder() -> GenericCardBuilder<'s>
+    {
+        GenericCardBuilder::default()
+    }
+
+    /// Copies this card into a card owning its data
+    pub fn to_owned_card(&self) -> OwnedCard
+    {
+        OwnedCard
+        {
+            serial: self.printing.serial.map(str::to_string),
+            name: self.oracle.name.to_string(),
+            cost: self.oracle.cost,
+            kind: self.oracle.kind.clone(),
+            rarity: self.printing.rarity,
+            effects: self.oracle.effects.to_string(),
+            artist: self.printing.artist.map(str::to_string),
+            language: self.printing.language,
+            frame: self.printing.frame,
+        }
+    }
+}
+
+/// The card's name, followed by its serial when it has one, as listed in reports
+/// ```
+/// use mtg_multitool::cards::GenericCard;
+/// let card = GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C;").unwrap() ;
+/// assert_eq!(card.to_string(), "Banish from Edoras (LTR C 0001)") ;
+/// assert_eq!(GenericCard::try_from(";Homemade card;1;Sorcery;C;").unwrap().to_string(), "Homemade card") ;
+/// ```
+impl std::fmt::Display for GenericCard<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", self.oracle.name)? ;
+        if let Some(serial) = self.printing.serial
+        {
+            write!(f, " ({})", serial)? ;
+        }
+        Ok(())
     }
 }
 
+/// Why a card could not be built
+#[derive(Debug, Clone, PartialEq)]
+pub enum CardError
+{
+    MissingName,
+    MissingCost,
+    MissingKind,
+    MissingRarity,
+    /// The serial is not formatted as `{series} {rarity} {internal series id}`
+    InvalidSerial(String),
+    /// The rarity found in the serial is not the rarity of the card
+    RarityMismatch { serial: Rarity, card: Rarity },
+}
+
+impl std::fmt::Display for CardError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            CardError::MissingName => write!(f, "No name given"),
+            CardError::MissingCost => write!(f, "No cost given"),
+            CardError::MissingKind => write!(f, "No card kind given"),
+            CardError::MissingRarity => write!(f, "No rarity given"),
+            CardError::InvalidSerial(serial) => write!(f, "Could not parse serial {:?}", serial),
+            CardError::RarityMismatch { serial, card } => write!(f, "Serial rarity ({:?}) differs from the card rarity ({:?})", serial, card),
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
+
+/// Builds a [`GenericCard`], see [`GenericCard::builder`]<br/>
+/// Name, cost, kind and rarity are mandatory, effects default to none
+#[derive(Debug, Clone, Default)]
+pub struct GenericCardBuilder<'a>
+{
+    serial: Option<&'a str>,
+    name: Option<&'a str>,
+    cost: Option<ManaCost>,
+    kind: Option<Classification>,
+    rarity: Option<Rarity>,
+    effects: &'a str,
+
+    artist: Option<&'a str>,
+    language: Language,
+    frame: Frame,
+}
+
+impl<'a> GenericCardBuilder<'a>
+{
+    /// Example: `"LTR C 0001"`
+    pub fn serial(mut self, serial: &'a str) -> Self
+    {
+        self.serial = Some(serial) ;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> Self
+    {
+        self.name = Some(name) ;
+        self
+    }
+
+    pub fn cost(mut self, cost: ManaCost) -> Self
+    {
+        self.cost = Some(cost) ;
+        self
+    }
+
+    pub fn kind(mut self, kind: Classification) -> Self
+    {
+        self.kind = Some(kind) ;
+        self
+    }
+
+    pub fn rarity(mut self, rarity: Rarity) -> Self
+    {
+        self.rarity = Some(rarity) ;
+        self
+    }
+
+    pub fn effects(mut self, effects: &'a str) -> Self
+    {
+        self.effects = effects ;
+        self
+    }
+
+    pub fn artist(mut self, artist: &'a str) -> Self
+    {
+        self.artist = Some(artist) ;
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self
+    {
+        self.language = language ;
+        self
+    }
+
+    pub fn frame(mut self, frame: Frame) -> Self
+    {
+        self.frame = frame ;
+        self
+    }
+
+    pub fn build(self) -> Result<GenericCard<'a>, CardError>
+    {
+        let name = self.name.filter(|name| !crate::names::normalize(name).is_empty()).ok_or(CardError::MissingName)? ;
+        let cost = self.cost.ok_or(CardError::MissingCost)? ;
+        let kind = self.kind.ok_or(CardError::MissingKind)? ;
+        let rarity = self.rarity.ok_or(CardError::MissingRarity)? ;
+
+        if let Some(serial) = self.serial
+        {
+            match crate::ids::parse_serial(serial)
+            {
+                None => return Err(CardError::InvalidSerial(serial.to_string())),
+                Some((_, Some(serial_rarity), _)) if serial_rarity != rarity => return Err(CardError::RarityMismatch { serial: serial_rarity, card: rarity }),
+                Some(_) => (),
+            }
+        }
+
+        Ok(
+            GenericCard
+            {
+                oracle: Arc::new(OracleCard { name, cost, kind, effects: self.effects }),
+                printing: Printing
+                {
+                    serial: self.serial,
+                    rarity,
+                    artist: self.artist,
+                    language: self.language,
+                    frame: self.frame,
+                },
+            }
+        )
+    }
+}
+
+#[test]
+fn genericcard_builder()
+{
+    use crate::Classification::{*} ;
+
+    let builder = GenericCard::builder()
+        .name("Bill the Poney")
+        .cost(ManaCost::try_from("3 w").unwrap())
+        .kind(Legendary(Box::new(Creature("Horse".to_string()))))
+        .rarity(Rarity::Uncommon) ;
+
+    let card = builder.clone().build().unwrap() ;
+    assert_eq!(*card.name(), "Bill the Poney") ;
+    assert_eq!(*card.effects(), "") ;
+    assert_eq!(card.printing().serial(), None) ;
+
+    let card = builder.clone().serial("LTR U 0003").artist("John Doe").language(Language::Italian).build().unwrap() ;
+    assert_eq!(card.printing().number(), Some(3)) ;
+    assert_eq!(card.printing().artist(), Some("John Doe")) ;
+    assert_eq!(card.printing().language(), &Language::Italian) ;
+
+    assert_eq!(builder.clone().serial("Bill").build().err(), Some(CardError::InvalidSerial("Bill".to_string()))) ;
+    assert_eq!(builder.clone().serial("LTR C 0003").build().err(), Some(CardError::RarityMismatch { serial: Rarity::Common, card: Rarity::Uncommon })) ;
+    assert!(builder.clone().serial("LTR-0003").build().is_ok()) ;
+    assert_eq!(builder.clone().name("  ").build().err(), Some(CardError::MissingName)) ;
+
+    assert_eq!(GenericCard::builder().build().err(), Some(CardError::MissingName)) ;
+    assert_eq!(GenericCard::builder().name("Bill the Poney").build().err(), Some(CardError::MissingCost)) ;
+    assert_eq!(GenericCard::builder().name("Bill the Poney").cost(ManaCost::zero()).build().err(), Some(CardError::MissingKind)) ;
+    assert_eq!(GenericCard::builder().name("Bill the Poney").cost(ManaCost::zero()).kind(Sorcery).build().err(), Some(CardError::MissingRarity)) ;
+}
+
+/// A card owning its data, for cards built at runtime and outliving their source
+#[derive(Debug, Clone)]
+pub struct OwnedCard
+{
+    serial: Option<String>,
+    name: String,
+
+    cost: ManaCost,
+    kind: Classification,
+    rarity: Rarity,
+
+    effects: String,
+
+    artist: Option<String>,
+    language: Language,
+    frame: Frame,
+}
+
+impl OwnedCard
+{
+    /// Borrows this card as a [`GenericCard`]
+    pub fn as_generic(&self) -> GenericCard<'_>
+    {
+        GenericCard
+        {
+            oracle: Arc::new(OracleCard { name: &self.name, cost: self.cost, kind: self.kind.clone(), effects: &self.effects }),
+            printing: Printing
+            {
+                serial: self.serial.as_deref(),
+                rarity: self.rarity,
+                artist: self.artist.as_deref(),
+                language: self.language,
+                frame: self.frame,
+            },
+        }
+    }
+}
+
+impl<'a> From<GenericCard<'a>> for OwnedCard
+{
+    fn from(value: GenericCard<'a>) -> Self
+    {
+        value.to_owned_card()
+    }
+}
+
+impl<'a> From<&'a OwnedCard> for crate::ids::Id<'a>
+{
+    fn from(value: &'a OwnedCard) -> Self
+    {
+        value.card_id()
+    }
+}
+
+/// Common accessors of borrowed ([`GenericCard`]) and owned ([`OwnedCard`]) cards
+pub trait Card
+{
+    fn name(&self) -> &str ;
+    fn cost(&self) -> ManaCost ;
+    fn kind(&self) -> &Classification ;
+    fn rarity(&self) -> Rarity ;
+    fn effects(&self) -> &str ;
+
+    /// Example: `Some("LTR C 0001")`
+    fn serial(&self) -> Option<&str> ;
+
+    /// The id of this card: its name, along with its serial if it has one
+    fn card_id(&self) -> crate::ids::Id<'_>
+    {
+        use crate::ids::Id::{Both, Name} ;
+        match self.serial()
+        {
+            Some(serial) => Both(self.name(), serial),
+            None => Name(self.name()),
+        }
+    }
+}
+
+impl Card for GenericCard<'_>
+{
+    fn name(&self) -> &str { self.oracle.name }
+    fn cost(&self) -> ManaCost { self.oracle.cost }
+    fn kind(&self) -> &Classification { &self.oracle.kind }
+    fn rarity(&self) -> Rarity { self.printing.rarity }
+    fn effects(&self) -> &str { self.oracle.effects }
+    fn serial(&self) -> Option<&str> { self.printing.serial }
+}
+
+impl Card for OwnedCard
+{
+    fn name(&self) -> &str { &self.name }
+    fn cost(&self) -> ManaCost { self.cost }
+    fn kind(&self) -> &Classification { &self.kind }
+    fn rarity(&self) -> Rarity { self.rarity }
+    fn effects(&self) -> &str { &self.effects }
+    fn serial(&self) -> Option<&str> { self.serial.as_deref() }
+}
+
+impl<C> Card for &C where C: Card + ?Sized
+{
+    fn name(&self) -> &str { (**self).name() }
+    fn cost(&self) -> ManaCost { (**self).cost() }
+    fn kind(&self) -> &Classification { (**self).kind() }
+    fn rarity(&self) -> Rarity { (**self).rarity() }
+    fn effects(&self) -> &str { (**self).effects() }
+    fn serial(&self) -> Option<&str> { (**self).serial() }
+}
+
+#[test]
+fn card_trait()
+{
+    fn describe(card: impl Card) -> String
+    {
+        format!("{} ({:?})", card.name(), card.rarity())
+    }
+
+    let owned = {
+        let line = String::from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.") ;
+        GenericCard::try_from(line.as_str()).unwrap().to_owned_card()
+    } ;
+    let borrowed = owned.as_generic() ;
+
+    assert_eq!(describe(&owned), "Banish from Edoras (Common)") ;
+    assert_eq!(describe(&borrowed), describe(borrowed.clone())) ;
+    assert_eq!(Card::serial(&owned), Some("LTR C 0001")) ;
+    assert_eq!(owned.card_id(), borrowed.id()) ;
+    assert_eq!(Card::cost(&owned).colorless(), 4) ;
+
+    let cards: Vec<Box<dyn Card>> = vec![Box::new(owned.clone()), Box::new(borrowed)] ;
+    assert!(cards.iter().all(|card| card.name() == "Banish from Edoras")) ;
+}
+
+/// The recognized format is `serial;name;cost;kind;rarity;effects`,
+/// optionally followed by `;artist;language;frame`
 impl<'s> TryFrom<&'s str> for GenericCard<'s>
 {
     type Error = &'static str;
@@ -53,7 +551,8 @@ impl<'s> TryFrom<&'s str> for GenericCard<'s>
     fn try_from(value: &'s str) -> Result<Self, Self::Error>
     {
 
-        let mut iter = value.split(";").map(str::trim) ;
+        // Quoted fields, such as `"Boromir, Warden of the Tower"`, are unquoted
+        let mut iter = value.split(";").map(|field| unquote(field.trim())) ;
 
         let id_str = iter.next() ;
         if let Some(name_str) = iter.next()
@@ -73,10 +572,18 @@ impl<'s> TryFrom<&'s str> for GenericCard<'s>
                             {
                                 let kind_conversion_result = Classification::try_from(kind_str);
 
-                                if kind_conversion_result.is_ok()
+                                if let Ok(kind) = kind_conversion_result
                                 {
                                     return if let Some(effects) = iter.next()
                                     {
+                                        let artist = iter.next().filter(|artist| !artist.is_empty()) ;
+                                        let language = match iter.next().filter(|language| !language.is_empty())
+                                        {
+                                            Some(language) => Language::try_from(language)?,
+                                            None => Language::default(),
+                                        } ;
+                                        let frame = Frame::try_from(iter.next().unwrap_or(""))? ;
+
                                         if iter.next().is_some()
                                         {
                                             Err("Too many fields")
@@ -84,12 +591,15 @@ impl<'s> TryFrom<&'s str> for GenericCard<'s>
                                             Ok(
                                                 GenericCard
                                                 {
-                                                    id: id_str,
-                                                    name: name_str,
-                                                    cost,
-                                                    kind: kind_conversion_result.unwrap(),
-                                                    rarity,
-                                                    effects,
+                                                    oracle: Arc::new(OracleCard { name: name_str, cost, kind, effects }),
+                                                    printing: Printing
+                                                    {
+                                                        serial: id_str.filter(|id| !id.is_empty()),
+                                                        rarity,
+                                                        artist,
+                                                        language,
+                                                        frame,
+                                                    },
                                                 }
                                             )
                                         }
@@ -114,14 +624,50 @@ impl<'s> TryFrom<&'s str> for GenericCard<'s>
             return Err("No Cost data found") ;
         }
 
-        return Err("No Name given") ;
+        Err("No Name given")
     }
 }
 
+/// Removes the double quotes surrounding a csv field, if any
+fn unquote(field: &str) -> &str
+{
+    field.strip_prefix('"')
+        .and_then(|f| f.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
 #[test]
 fn genericcard_try_from_str()
 {
-    todo!()
+    use crate::Classification::{*} ;
+
+    let card = GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.").unwrap() ;
+    assert_eq!(card.printing().serial(), Some("LTR C 0001")) ;
+    assert_eq!(card.printing().set(), Some("LTR".to_string())) ;
+    assert_eq!(card.printing().number(), Some(1)) ;
+    assert_eq!(card.printing().artist(), None) ;
+    assert_eq!(card.printing().language(), &Language::English) ;
+    assert_eq!(*card.name(), "Banish from Edoras") ;
+    assert_eq!(card.cost().colorless(), 4) ;
+    assert_eq!(card.cost().white(), 1) ;
+    assert_eq!(card.kind(), &Sorcery) ;
+    assert_eq!(card.rarity(), &Rarity::Common) ;
+    assert_eq!(*card.effects(), "Exile target creature.") ;
+
+    let card = GenericCard::try_from(r#"LTR R 0004;"Boromir, Warden of the Tower";2 w;Legendary Creature - Human Soldier;R;"Vigilence""#).unwrap() ;
+    assert_eq!(*card.name(), "Boromir, Warden of the Tower") ;
+    assert_eq!(*card.effects(), "Vigilence") ;
+    assert_eq!(card.kind(), &Legendary(Box::new(Creature("Human Soldier".to_string())))) ;
+
+    assert!(GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C").is_err()) ;
+    assert!(GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;Z;Exile target creature.").is_err()) ;
+    let card = GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.;Jane Doe;fr;showcase").unwrap() ;
+    assert_eq!(card.printing().artist(), Some("Jane Doe")) ;
+    assert_eq!(card.printing().language(), &Language::French) ;
+    assert_eq!(card.printing().frame(), &Frame::Showcase) ;
+
+    assert!(GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.;Jane Doe;Elvish").is_err()) ;
+    assert!(GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.;;;;Extra").is_err()) ;
 }
 
 impl<'a> From<GenericCard<'a>> for crate::ids::Id<'a>
@@ -129,17 +675,23 @@ impl<'a> From<GenericCard<'a>> for crate::ids::Id<'a>
     fn from(value: GenericCard<'a>) -> Self
     {
         use crate::ids::Id::{Both, Name} ;
-        if let Some(id) = value.id
+        if let Some(id) = value.printing.serial
         {
-            return Both(value.name, id) ;
+            return Both(value.oracle.name, id) ;
         }
 
-        return Name(value.name) ;
+        Name(value.oracle.name)
     }
 }
 
 #[test]
 fn id_from_generic_card()
 {
-    todo!()
+    use crate::ids::Id ;
+
+    let card = GenericCard::try_from("LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.").unwrap() ;
+    assert!(matches!(Id::from(card), Id::Both("Banish from Edoras", "LTR C 0001"))) ;
+
+    let card = GenericCard::try_from(";Banish from Edoras;4 w;Sorcery;C;Exile target creature.").unwrap() ;
+    assert!(matches!(Id::from(card), Id::Name("Banish from Edoras"))) ;
 }
\ No newline at end of file
@@ -1,69 +1,1418 @@
-use crate::cards::GenericCard;
-use crate::ids::{AsId, Id};
+use std::collections::HashMap;
 
-#[derive(Debug)]
-pub struct Collection<'a>(Vec<(GenericCard<'a>, usize)>) ;
+use crate::cards::{GenericCard, OracleCard};
+use crate::ids::{AsId, Id, Policy, ResolveError};
+use crate::stock::{Stock, Variant};
+
+/// Cards along with their owned quantities<br/>
+/// Cards are kept in collector-number order (sets being kept in the order they were first seen),
+/// and indexed by normalized name and by serial, so that lookups do not depend on the collection size<br/>
+/// <br/>
+/// Operations act on exactly one card, the one the id resolves to (see [`crate::ids::resolve`]):
+/// <ul>
+/// <li>`Name` matches every printing with that name</li>
+/// <li>`Serial` matches the printing with that serial</li>
+/// <li>`Both` matches the printing with that serial, only if it also has that name</li>
+/// <li>`Either` matches the printings having that name, and the one having that serial:
+/// if they are different cards, the id is ambiguous</li>
+/// </ul>
+/// When an id matches several cards, the collection's [`Policy`] decides, and defaults to an
+/// [`CollectionError::AmbiguousId`] error. Failed operations leave the collection untouched.
+#[derive(Debug, Clone)]
+pub struct Collection<'a>
+{
+    cards: Vec<(GenericCard<'a>, Stock)>,
+
+    /// Normalized names (see [`crate::names::normalize`]) to positions in `cards`
+    by_name: HashMap<String, Vec<usize>>,
+    /// Serial keys (see [`serial_key`]) to positions in `cards`
+    by_serial: HashMap<String, Vec<usize>>,
+
+    /// Used to pick a card when an id matches several of them
+    policy: Policy,
+}
 
 impl<'gc> Collection<'gc>
 {
-    pub fn make<T>(items: Vec<T>) -> Collection<'gc> //Result<Collection<'gc>, &'static str>
-        where T: TryInto<GenericCard<'gc>> + Clone
+    /// Builds a collection out of cards (or csv lines), with a count of 0 for each of them
+    pub fn make<T>(items: Vec<T>) -> Result<Collection<'gc>, CollectionError<'gc>>
+        where T: TryInto<GenericCard<'gc>>,
+              T::Error: std::fmt::Display
     {
-        Collection(
-            items.iter().map(
-                |item|
-                    {
-                        if let Ok(i) = item.clone().try_into()
-                        {
-                            (i, 0usize)
-                        } else {
-                            panic!("Collection::make Could not clone input card") // todo: remplacer par un Result
-                        }
+        let mut cards = Vec::with_capacity(items.len()) ;
+        for (index, item) in items.into_iter().enumerate()
+        {
+            match item.try_into()
+            {
+                Ok(card) => cards.push((card, Stock::default())),
+                Err(e) => return Err(CollectionError::InvalidCard { index, reason: e.to_string() }),
+            }
+        }
 
-                    }
-            ).collect()
-        )
+        Ok(Collection::from_entries(cards))
     }
 
-    pub fn add<'any, T>(&mut self, id: T, quantity: usize)
-        where T: AsId<'any>
+    /// Sorts the entries in collector-number order, then indexes them
+    fn from_entries(cards: Vec<(GenericCard<'gc>, Stock)>) -> Collection<'gc>
+    {
+        let mut collection = Collection
+        {
+            cards,
+            by_name: HashMap::new(),
+            by_serial: HashMap::new(),
+            policy: Policy::default(),
+        } ;
+        collection.rebuild() ;
+        collection
+    }
+
+    /// Sorts the entries in collector-number order, then indexes them again
+    fn rebuild(&mut self)
+    {
+        let mut sets: Vec<String> = vec![] ;
+        let mut keyed: Vec<((usize, usize), (GenericCard<'gc>, Stock))> = std::mem::take(&mut self.cards).into_iter()
+            .map(|entry| {
+                let key = match (entry.0.printing().set(), entry.0.printing().number())
+                {
+                    (Some(set), Some(number)) => {
+                        let rank = sets.iter().position(|known| *known == set).unwrap_or_else(|| {
+                            sets.push(set) ;
+                            sets.len() - 1
+                        }) ;
+                        (rank, number)
+                    },
+                    _ => (usize::MAX, 0),
+                } ;
+                (key, entry)
+            })
+            .collect() ;
+
+        // Stable sort: cards without collector number keep their relative order, at the end
+        keyed.sort_by_key(|(key, _)| *key) ;
+        self.cards = keyed.into_iter().map(|(_, entry)| entry).collect() ;
+        self.reindex() ;
+    }
+
+    /// Indexes the cards again, printings of the same card sharing the oracle record of the first one
+    fn reindex(&mut self)
+    {
+        self.by_name.clear() ;
+        self.by_serial.clear() ;
+
+        for index in 0..self.cards.len()
+        {
+            let printings = self.by_name.entry(crate::names::normalize(self.cards[index].0.name())).or_default() ;
+            if let Some(&first) = printings.first()
+            {
+                let (before, after) = self.cards.split_at_mut(index) ;
+                after[0].0.share_oracle(&before[first].0) ;
+            }
+            printings.push(index) ;
+
+            if let Some(serial) = self.cards[index].0.printing().serial()
+            {
+                self.by_serial.entry(serial_key(serial)).or_default().push(index) ;
+            }
+        }
+    }
+
+    /// Builds a collection with the cards of a catalog (such as `ltr::collection()`) and the given counts
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::ltr;
+    ///
+    /// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 2), ("LTR R 0004", 1)]).unwrap() ;
+    /// assert_eq!(collection.len(), 5) ;
+    /// assert_eq!(collection.total(), 3) ;
+    /// ```
+    pub fn from_counts<'any, I, T>(catalog: &Collection<'gc>, counts: I) -> Result<Collection<'gc>, CollectionError<'gc>>
+        where I: IntoIterator<Item = (T, usize)>,
+              T: AsId<'any>
+    {
+        let mut collection = Collection
+        {
+            cards: catalog.cards.iter().map(|(card, _)| (card.clone(), Stock::default())).collect(),
+            by_name: catalog.by_name.clone(),
+            by_serial: catalog.by_serial.clone(),
+            policy: catalog.policy.clone(),
+        } ;
+        collection.add_all(counts)? ;
+        Ok(collection)
+    }
+
+    /// Adds copies of several cards at once; if any id cannot be resolved, or any count would overflow, nothing is added
+    pub fn add_all<'any, I, T>(&mut self, counts: I) -> Result<(), CollectionError<'gc>>
+        where I: IntoIterator<Item = (T, usize)>,
+              T: AsId<'any>
+    {
+        let positions = counts.into_iter()
+            .map(|(id, quantity)| self.position(id, &self.policy).map(|index| (index, quantity)))
+            .collect::<Result<Vec<(usize, usize)>, ResolveError<'gc>>>()? ;
+
+        let mut added: HashMap<usize, usize> = HashMap::new() ;
+        for &(index, quantity) in &positions
+        {
+            let before = added.get(&index).copied().unwrap_or(0) ;
+            let owned = self.cards[index].1.total() + before ;
+            owned.checked_add(quantity).ok_or(CollectionError::TooManyCopies { owned, added: quantity })? ;
+            added.insert(index, before + quantity) ;
+        }
+
+        for (index, quantity) in positions
+        {
+            let (card, stock) = &mut self.cards[index] ;
+            stock.add(Variant::of(card.printing()), quantity) ;
+        }
+
+        Ok(())
+    }
+
+    /// Number of different cards (printings) in the collection, owned or not
+    pub fn len(&self) -> usize
+    {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.cards.is_empty()
+    }
+
+    /// Total number of copies owned
+    pub fn total(&self) -> usize
+    {
+        self.cards.iter().map(|(_, stock)| stock.total()).sum()
+    }
+
+    /// Iterates over the cards and their counts, in collector-number order
+    pub fn iter(&self) -> Iter<'_, 'gc>
+    {
+        Iter(self.cards.iter())
+    }
+
+    /// Iterates over the cards and their copies broken down by variant, in collector-number order
+    pub fn stocks(&self) -> impl Iterator<Item = (&GenericCard<'gc>, &Stock)>
+    {
+        self.cards.iter().map(|(card, stock)| (card, stock))
+    }
+
+    /// Iterates over the cards owned at least once, in collector-number order
+    pub fn owned(&self) -> impl Iterator<Item = (&GenericCard<'gc>, usize)>
+    {
+        self.iter().filter(|(_, count)| *count > 0)
+    }
+
+    /// Iterates over the cards not owned, in collector-number order
+    pub fn missing(&self) -> impl Iterator<Item = (&GenericCard<'gc>, usize)>
     {
-        let id: Id = id.as_id() ;
-        for (card, amount) in self.0.iter_mut()
+        self.iter().filter(|(_, count)| *count == 0)
+    }
+
+    /// Sums the copies of both collections, variant by variant; cards only found in `other` are added
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::ltr;
+    ///
+    /// let mine = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 2)]).unwrap() ;
+    /// let yours = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 1), ("Bill the Poney", 1)]).unwrap() ;
+    ///
+    /// assert_eq!(mine.union(&yours).total(), 4) ;
+    /// assert_eq!(mine.difference(&yours).count("Banish from Edoras"), Some(1)) ;
+    /// assert_eq!(mine.intersection(&yours).total(), 1) ;
+    /// ```
+    pub fn union(&self, other: &Collection<'gc>) -> Collection<'gc>
+    {
+        let (stocks, unmatched) = self.match_stocks(other) ;
+        let mut result = self.clone() ;
+        for (entry, stock) in result.cards.iter_mut().zip(stocks)
         {
-            if id == card.as_id()
+            entry.1.merge(&stock) ;
+        }
+        result.extend(unmatched) ;
+        result
+    }
+
+    /// Removes as many copies as found in `other` (a decklist for instance), stopping at 0;
+    /// cards of `other` that cannot be found in this collection are ignored<br/>
+    /// Copies are removed regardless of their variant, see [`Stock::take`]
+    pub fn difference(&self, other: &Collection<'gc>) -> Collection<'gc>
+    {
+        let (stocks, _) = self.match_stocks(other) ;
+        let mut result = self.clone() ;
+        for (entry, stock) in result.cards.iter_mut().zip(stocks)
+        {
+            entry.1.take(stock.total()) ;
+        }
+        result
+    }
+
+    /// Keeps as many copies as found in both collections, regardless of their variant
+    pub fn intersection(&self, other: &Collection<'gc>) -> Collection<'gc>
+    {
+        let (stocks, _) = self.match_stocks(other) ;
+        let mut result = self.clone() ;
+        for (entry, stock) in result.cards.iter_mut().zip(stocks)
+        {
+            let extra = entry.1.total().saturating_sub(stock.total()) ;
+            entry.1.take(extra) ;
+        }
+        result
+    }
+
+    /// Lists what changed between this collection and a newer snapshot of it<br/>
+    /// Counts are compared per card, regardless of their variant
+    pub fn diff(&self, newer: &Collection<'gc>) -> CollectionDiff<'gc>
+    {
+        let (stocks, unmatched) = self.match_stocks(newer) ;
+        let changes = self.cards.iter()
+            .zip(stocks)
+            .map(|((card, before), after)| (card.clone(), before.total(), after.total()))
+            .chain(unmatched.into_iter().map(|(card, after)| (card, 0, after.total())))
+            .filter(|(_, before, after)| before != after)
+            .map(|(card, before, after)| CardChange { card, before, after })
+            .collect() ;
+
+        CollectionDiff { changes }
+    }
+
+    /// Copies of `other` for each card of this collection, along with the cards of `other` not found in it<br/>
+    /// Cards are matched by printing first, then by resolving their id with this collection's policy
+    pub(crate) fn match_stocks(&self, other: &Collection<'gc>) -> (Vec<Stock>, Vec<(GenericCard<'gc>, Stock)>)
+    {
+        let mut stocks = vec![Stock::default() ; self.cards.len()] ;
+        let mut unmatched = vec![] ;
+
+        for (card, stock) in &other.cards
+        {
+            match self.find_printing(card).or_else(|| self.position(card.as_id(), &self.policy).ok())
             {
-                *amount += quantity ;
-                break ;
+                Some(index) => stocks[index].merge(stock),
+                None => unmatched.push((card.clone(), stock.clone())),
             }
         }
+
+        (stocks, unmatched)
+    }
+
+    /// Position of the very same printing in this collection, if any
+    fn find_printing(&self, card: &GenericCard<'_>) -> Option<usize>
+    {
+        self.candidates(card.as_id())
+            .into_iter()
+            .find(|&index| {
+                match (self.cards[index].0.printing().serial(), card.printing().serial())
+                {
+                    (Some(serial), Some(other)) => serial_key(serial) == serial_key(other),
+                    (None, None) => true,
+                    _ => false,
+                }
+            })
+    }
+
+    /// Sets the policy used by `add`, `remove` and `count` when an id matches several cards
+    pub fn with_policy(mut self, policy: Policy) -> Collection<'gc>
+    {
+        self.policy = policy ;
+        self
+    }
+
+    pub fn policy(&self) -> &Policy
+    {
+        &self.policy
+    }
+
+    /// Resolves an id to exactly one card of this collection, according to the given policy<br/>
+    /// Cards are expected to be listed from the oldest printing to the newest one
+    pub fn resolve<'any, T>(&self, id: T, policy: &Policy) -> Result<&GenericCard<'gc>, ResolveError<'gc>>
+        where T: AsId<'any>
+    {
+        self.position(id, policy).map(|index| &self.cards[index].0)
+    }
+
+    /// Resolves an id to an oracle card: a name matches every printing of the card, a serial its printing's card
+    pub fn oracle<'any, T>(&self, id: T) -> Result<&OracleCard<'gc>, ResolveError<'gc>>
+        where T: AsId<'any>
+    {
+        let printings: Vec<&GenericCard<'gc>> = self.candidates(id.as_id())
+            .into_iter()
+            .map(|index| &self.cards[index].0)
+            .collect() ;
+
+        match printings.split_first()
+        {
+            None => Err(ResolveError::NotFound),
+            Some((first, others)) if others.iter().all(|card| card.shares_oracle(first)) => Ok(first.oracle()),
+            Some(_) => Err(ResolveError::Ambiguous(printings.iter().map(|card| card.as_id()).collect())),
+        }
     }
 
-    pub fn remove<'any, T>(&mut self, id: T, quantity: usize)
+    /// Every printing of the oracle card the id resolves to, from the oldest to the newest, all sharing its oracle record
+    pub fn printings<'any, T>(&self, id: T) -> Result<Vec<&GenericCard<'gc>>, ResolveError<'gc>>
         where T: AsId<'any>
     {
-        let id: Id = id.as_id() ;
-        for (card, amount) in self.0.iter_mut() {
-            if id == card.as_id()
+        let oracle = self.oracle(id)? ;
+        Ok(
+            self.by_name.get(&crate::names::normalize(oracle.name()))
+                .into_iter()
+                .flatten()
+                .map(|&index| &self.cards[index].0)
+                .collect()
+        )
+    }
+
+    /// Positions of the cards matching the id, in collection order
+    fn candidates(&self, id: Id<'_>) -> Vec<usize>
+    {
+        let mut candidates: Vec<usize> = vec![] ;
+        if let Some(name) = id.name()
+        {
+            candidates.extend(self.by_name.get(&crate::names::normalize(name)).into_iter().flatten()) ;
+        }
+        if let Some(serial) = id.serial()
+        {
+            candidates.extend(self.by_serial.get(&serial_key(serial)).into_iter().flatten()) ;
+        }
+
+        candidates.sort_unstable() ;
+        candidates.dedup() ;
+        candidates.retain(|&index| self.cards[index].0.as_id() == id) ;
+        candidates
+    }
+
+    fn position<'any, T>(&self, id: T, policy: &Policy) -> Result<usize, ResolveError<'gc>>
+        where T: AsId<'any>
+    {
+        let candidates = self.candidates(id.as_id()) ;
+        crate::ids::resolve(id.as_id(), candidates.iter().map(|&index| self.cards[index].0.as_id()), policy)
+            .map(|position| candidates[position])
+    }
+
+    /// Adds nonfoil, near mint copies of a card, in its printing's language (see [`Variant::of`]), returning its new count<br/>
+    /// See [`Collection`] for how ids are resolved
+    pub fn add<'any, T>(&mut self, id: T, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let index = self.position(id, &self.policy)? ;
+        let (card, stock) = &mut self.cards[index] ;
+        let total = room_for(stock, quantity)? ;
+        stock.add(Variant::of(card.printing()), quantity) ;
+        Ok(total)
+    }
+
+    /// Adds copies of a variant of a card, returning the card's new count (all variants included)
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::stock::Variant;
+    /// use mtg_multitool::{ltr, Condition, Finish, Language};
+    ///
+    /// let mut collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 2)]).unwrap() ;
+    /// let foil = Variant::new(Finish::Foil, Condition::LightlyPlayed, Language::French) ;
+    ///
+    /// assert_eq!(collection.add_variant("Banish from Edoras", foil, 1), Ok(3)) ;
+    /// assert_eq!(collection.count("Banish from Edoras"), Some(3)) ;
+    /// assert_eq!(collection.count_variant("Banish from Edoras", &foil), Some(1)) ;
+    /// ```
+    pub fn add_variant<'any, T>(&mut self, id: T, variant: Variant, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let index = self.position(id, &self.policy)? ;
+        let stock = &mut self.cards[index].1 ;
+        let total = room_for(stock, quantity)? ;
+        stock.add(variant, quantity) ;
+        Ok(total)
+    }
+
+    /// Adds copies of a variant of a card at a location (such as `"Binder 2, page 5"`),
+    /// returning the card's new count (all variants and locations included)<br/>
+    /// Locations may not contain `;` nor line breaks (see [`CollectionError::ReservedCharacter`])
+    pub fn add_at<'any, T>(&mut self, id: T, variant: Variant, location: &str, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        check_label(location)? ;
+        let index = self.position(id, &self.policy)? ;
+        let stock = &mut self.cards[index].1 ;
+        let total = room_for(stock, quantity)? ;
+        stock.add_at(variant, Some(location), quantity) ;
+        Ok(total)
+    }
+
+    /// Moves copies of a card from a location to another (`None` for unsorted copies), keeping their variant;
+    /// returns the number of copies now at the destination, the card's count being unchanged<br/>
+    /// Nothing is moved if there are less than `quantity` copies at `from`
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::ltr;
+    ///
+    /// let mut collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4)]).unwrap() ;
+    /// assert_eq!(collection.move_copies("Banish from Edoras", None, Some("Binder 2, page 5"), 3), Ok(3)) ;
+    /// assert_eq!(collection.move_copies("Banish from Edoras", Some("Binder 2, page 5"), Some("Boromir deck"), 1), Ok(1)) ;
+    ///
+    /// assert_eq!(collection.in_location(Some("binder 2, page 5")).map(|(card, count)| (*card.name(), count)).collect::<Vec<_>>(), vec![("Banish from Edoras", 2)]) ;
+    /// assert_eq!(collection.count("Banish from Edoras"), Some(4)) ;
+    /// ```
+    pub fn move_copies<'any, T>(&mut self, id: T, from: Option<&str>, to: Option<&str>, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        check_label(to.unwrap_or_default())? ;
+        let index = self.position(id, &self.policy)? ;
+        let stock = &mut self.cards[index].1 ;
+        stock.move_copies(from, to, quantity)
+            .ok_or(CollectionError::InsufficientQuantity { owned: stock.at(from), requested: quantity })
+    }
+
+    /// Iterates over the cards having copies at a location (`None` for unsorted copies), and their number of copies there
+    pub fn in_location<'c>(&'c self, location: Option<&'c str>) -> impl Iterator<Item = (&'c GenericCard<'gc>, usize)> + 'c
+    {
+        self.cards.iter()
+            .map(move |(card, stock)| (card, stock.at(location)))
+            .filter(|(_, count)| *count > 0)
+    }
+
+    /// Every location holding copies, in the order they are first found in the collection
+    pub fn locations(&self) -> Vec<&str>
+    {
+        let mut locations: Vec<&str> = vec![] ;
+        for lot in self.cards.iter().flat_map(|(_, stock)| stock.lots())
+        {
+            if let Some(location) = lot.location.as_deref()
             {
-                *amount -= quantity ;
+                if !locations.iter().any(|known| crate::names::same_name(known, location))
+                {
+                    locations.push(location) ;
+                }
             }
         }
+        locations
+    }
+
+    /// Tags a card (`"trade"`, `"keep"`, `"proxy"`...), returning `false` if it already had that tag<br/>
+    /// Tags may not contain `;` nor line breaks (see [`CollectionError::ReservedCharacter`])
+    pub fn tag<'any, T>(&mut self, id: T, tag: &str) -> Result<bool, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        check_label(tag)? ;
+        let index = self.position(id, &self.policy)? ;
+        Ok(self.cards[index].1.tag(tag))
+    }
+
+    /// Removes a tag from a card, returning `false` if it did not have that tag
+    pub fn untag<'any, T>(&mut self, id: T, tag: &str) -> Result<bool, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let index = self.position(id, &self.policy)? ;
+        Ok(self.cards[index].1.untag(tag))
+    }
+
+    /// Iterates over the cards having a tag, and their counts
+    pub fn tagged<'c>(&'c self, tag: &'c str) -> impl Iterator<Item = (&'c GenericCard<'gc>, usize)> + 'c
+    {
+        self.cards.iter()
+            .filter(move |(_, stock)| stock.has_tag(tag))
+            .map(|(card, stock)| (card, stock.total()))
+    }
+
+    /// Removes copies of a card, returning its new count<br/>
+    /// Copies are removed regardless of their variant (see [`Stock::take`]).
+    /// This is the all-or-nothing (try) removal: nothing is removed if there are less than `quantity` copies,
+    /// whereas [`Collection::saturating_remove`] removes as many as there are
+    pub fn remove<'any, T>(&mut self, id: T, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let index = self.position(id, &self.policy)? ;
+        let stock = &mut self.cards[index].1 ;
+        let owned = stock.total() ;
+        if owned < quantity
+        {
+            return Err(CollectionError::InsufficientQuantity { owned, requested: quantity }) ;
+        }
+
+        stock.take(quantity) ;
+        Ok(owned - quantity)
+    }
+
+    /// Removes copies of a variant of a card, returning the card's new count (all variants included)<br/>
+    /// Nothing is removed if there are less than `quantity` copies of that variant
+    pub fn remove_variant<'any, T>(&mut self, id: T, variant: &Variant, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let index = self.position(id, &self.policy)? ;
+        let stock = &mut self.cards[index].1 ;
+        match stock.remove(variant, quantity)
+        {
+            Some(_) => Ok(stock.total()),
+            None => Err(CollectionError::InsufficientQuantity { owned: stock.get(variant), requested: quantity }),
+        }
     }
 
+    /// Removes copies of a variant of a card at a location (`None` for unsorted copies), returning the card's new count<br/>
+    /// Nothing is removed if there are less than `quantity` copies of that variant there
+    pub fn remove_at<'any, T>(&mut self, id: T, variant: &Variant, location: Option<&str>, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let index = self.position(id, &self.policy)? ;
+        let stock = &mut self.cards[index].1 ;
+        match stock.remove_at(variant, location, quantity)
+        {
+            Some(_) => Ok(stock.total()),
+            None => Err(CollectionError::InsufficientQuantity { owned: stock.count_at(variant, location), requested: quantity }),
+        }
+    }
+
+    /// Removes up to `quantity` copies of a card, regardless of their variant, stopping at 0, and returns its new count
+    pub fn saturating_remove<'any, T>(&mut self, id: T, quantity: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let index = self.position(id, &self.policy)? ;
+        let stock = &mut self.cards[index].1 ;
+        stock.take(quantity) ;
+        Ok(stock.total())
+    }
+
+    /// Number of copies of a card (all variants included), `None` if the id does not resolve to exactly one card
     pub fn count<'any, T>(&self, id: T) -> Option<usize>
         where T: AsId<'any>
     {
-        for (card, amount) in &self.0
+        self.stock(id).map(Stock::total)
+    }
+
+    /// Number of copies of a variant of a card, `None` if the id does not resolve to exactly one card
+    pub fn count_variant<'any, T>(&self, id: T, variant: &Variant) -> Option<usize>
+        where T: AsId<'any>
+    {
+        self.stock(id).map(|stock| stock.get(variant))
+    }
+
+    /// Copies of a card, broken down by variant and location, `None` if the id does not resolve to exactly one card
+    pub fn stock<'any, T>(&self, id: T) -> Option<&Stock>
+        where T: AsId<'any>
+    {
+        self.position(id, &self.policy)
+            .ok()
+            .map(|index| &self.cards[index].1)
+    }
+}
+
+/// How the count of a card changed between two snapshots, see [`Collection::diff`]
+#[derive(Debug, Clone)]
+pub struct CardChange<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub before: usize,
+    pub after: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind
+{
+    /// The card was not owned before
+    Added,
+    /// The card is not owned anymore
+    Removed,
+    /// The card is still owned, in a different quantity
+    Changed,
+}
+
+impl CardChange<'_>
+{
+    pub fn kind(&self) -> ChangeKind
+    {
+        match (self.before, self.after)
+        {
+            (0, _) => ChangeKind::Added,
+            (_, 0) => ChangeKind::Removed,
+            _ => ChangeKind::Changed,
+        }
+    }
+
+    /// Number of copies gained (or lost, if negative)
+    pub fn delta(&self) -> isize
+    {
+        self.after as isize - self.before as isize
+    }
+}
+
+/// Structured differences between two collections, see [`Collection::diff`]
+#[derive(Debug, Clone)]
+pub struct CollectionDiff<'gc>
+{
+    /// Changed cards only, in collector-number order of the older collection, then new cards
+    pub changes: Vec<CardChange<'gc>>,
+}
+
+impl<'gc> CollectionDiff<'gc>
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.changes.is_empty()
+    }
+
+    pub fn added(&self) -> impl Iterator<Item = &CardChange<'gc>>
+    {
+        self.changes.iter().filter(|change| change.kind() == ChangeKind::Added)
+    }
+
+    pub fn removed(&self) -> impl Iterator<Item = &CardChange<'gc>>
+    {
+        self.changes.iter().filter(|change| change.kind() == ChangeKind::Removed)
+    }
+
+    pub fn changed(&self) -> impl Iterator<Item = &CardChange<'gc>>
+    {
+        self.changes.iter().filter(|change| change.kind() == ChangeKind::Changed)
+    }
+}
+
+/// One line per change, such as `+2 Banish from Edoras (LTR C 0001): 1 -> 3`
+impl std::fmt::Display for CollectionDiff<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        if self.is_empty()
         {
-            if card.as_id() == id.as_id()
+            return writeln!(f, "No changes") ;
+        }
+
+        for change in &self.changes
+        {
+            let label = match change.kind()
+            {
+                ChangeKind::Added => "added",
+                ChangeKind::Removed => "removed",
+                ChangeKind::Changed => "changed",
+            } ;
+            write!(f, "{:+} {}", change.delta(), change.card)? ;
+            writeln!(f, ": {} -> {} ({})", change.before, change.after, label)? ;
+        }
+
+        let (gained, lost) = self.changes.iter()
+            .fold((0, 0), |(gained, lost), change| match change.delta()
             {
-                return Some(amount.clone()) ;
+                delta if delta > 0 => (gained + delta, lost),
+                delta => (gained, lost - delta),
+            }) ;
+        writeln!(f, "{} cards changed: {} copies gained, {} copies lost", self.changes.len(), gained, lost)
+    }
+}
+
+/// Iterator over the cards of a collection and their counts, see [`Collection::iter`]
+#[derive(Debug, Clone)]
+pub struct Iter<'c, 'gc>(std::slice::Iter<'c, (GenericCard<'gc>, Stock)>) ;
+
+impl<'c, 'gc> Iterator for Iter<'c, 'gc>
+{
+    type Item = (&'c GenericCard<'gc>, usize) ;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        self.0.next().map(|(card, stock)| (card, stock.total()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_, '_>
+{
+    fn next_back(&mut self) -> Option<Self::Item>
+    {
+        self.0.next_back().map(|(card, stock)| (card, stock.total()))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_, '_> {}
+
+impl<'c, 'gc> IntoIterator for &'c Collection<'gc>
+{
+    type Item = (&'c GenericCard<'gc>, usize) ;
+    type IntoIter = Iter<'c, 'gc> ;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        self.iter()
+    }
+}
+
+impl<'gc> IntoIterator for Collection<'gc>
+{
+    type Item = (GenericCard<'gc>, usize) ;
+    type IntoIter = IntoIter<'gc> ;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        IntoIter(self.cards.into_iter())
+    }
+}
+
+/// Owning iterator over the cards of a collection and their counts
+#[derive(Debug)]
+pub struct IntoIter<'gc>(std::vec::IntoIter<(GenericCard<'gc>, Stock)>) ;
+
+impl<'gc> Iterator for IntoIter<'gc>
+{
+    type Item = (GenericCard<'gc>, usize) ;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        self.0.next().map(|(card, stock)| (card, stock.total()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IntoIter<'_>
+{
+    fn next_back(&mut self) -> Option<Self::Item>
+    {
+        self.0.next_back().map(|(card, stock)| (card, stock.total()))
+    }
+}
+
+impl ExactSizeIterator for IntoIter<'_> {}
+
+/// Adds the cards with their copies: copies of a printing already in the collection are added to its stock
+impl<'gc> Extend<(GenericCard<'gc>, Stock)> for Collection<'gc>
+{
+    fn extend<I: IntoIterator<Item = (GenericCard<'gc>, Stock)>>(&mut self, iter: I)
+    {
+        let mut inserted = false ;
+        for (card, stock) in iter
+        {
+            match self.find_printing(&card)
+            {
+                Some(index) => self.cards[index].1.merge(&stock),
+                None => {
+                    // Indexed right away, so that later duplicates are found
+                    let index = self.cards.len() ;
+                    self.by_name.entry(crate::names::normalize(card.name())).or_default().push(index) ;
+                    if let Some(serial) = card.printing().serial()
+                    {
+                        self.by_serial.entry(serial_key(serial)).or_default().push(index) ;
+                    }
+                    self.cards.push((card, stock)) ;
+                    inserted = true ;
+                },
             }
         }
 
-        None
+        if inserted
+        {
+            self.rebuild() ;
+        }
+    }
+}
+
+/// Copies are of the default [`Variant`]
+impl<'gc> Extend<(GenericCard<'gc>, usize)> for Collection<'gc>
+{
+    fn extend<I: IntoIterator<Item = (GenericCard<'gc>, usize)>>(&mut self, iter: I)
+    {
+        self.extend(iter.into_iter().map(|(card, count)| (card, Stock::from(count)))) ;
+    }
+}
+
+/// Each card counts as one copy
+impl<'gc> Extend<GenericCard<'gc>> for Collection<'gc>
+{
+    fn extend<I: IntoIterator<Item = GenericCard<'gc>>>(&mut self, iter: I)
+    {
+        self.extend(iter.into_iter().map(|card| (card, 1))) ;
+    }
+}
+
+impl<'gc> FromIterator<(GenericCard<'gc>, usize)> for Collection<'gc>
+{
+    fn from_iter<I: IntoIterator<Item = (GenericCard<'gc>, usize)>>(iter: I) -> Self
+    {
+        let mut collection = Collection::from_entries(vec![]) ;
+        collection.extend(iter) ;
+        collection
+    }
+}
+
+/// Each card counts as one copy
+/// ```
+/// use mtg_multitool::cards::GenericCard;
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::ltr;
+///
+/// let pile = [ltr::BANISH_FROM_EDORAS, ltr::BILL_THE_PONEY, ltr::BANISH_FROM_EDORAS] ;
+/// let collection: Collection = pile.into_iter().map(|line| GenericCard::try_from(line).unwrap()).collect() ;
+/// assert_eq!(collection.len(), 2) ;
+/// assert_eq!(collection.count("Banish from Edoras"), Some(2)) ;
+/// ```
+impl<'gc> FromIterator<GenericCard<'gc>> for Collection<'gc>
+{
+    fn from_iter<I: IntoIterator<Item = GenericCard<'gc>>>(iter: I) -> Self
+    {
+        iter.into_iter().map(|card| (card, 1)).collect()
+    }
+}
+
+/// Why a collection operation failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollectionError<'c>
+{
+    /// An item given to [`Collection::make`] is not a card
+    InvalidCard { index: usize, reason: String },
+    /// No card of the collection matches the id
+    UnknownCard,
+    /// Several cards of the collection match the id, and the policy could not choose between them
+    AmbiguousId(Vec<Id<'c>>),
+    /// Less copies are owned than were asked to be removed
+    InsufficientQuantity { owned: usize, requested: usize },
+    /// Adding the copies would overflow the count of the card
+    TooManyCopies { owned: usize, added: usize },
+    /// A location or tag contains a `;` or a line break, which separate the fields and entries of collection files (see [`crate::persistence`])
+    ReservedCharacter(String),
+}
+
+/// Catalog shared by the tests of the modules built on collections: Banish from Edoras printed in LTR then in LTC,
+/// Bill the Poney, Boromir, and a card without serial
+#[cfg(test)]
+pub(crate) fn test_catalog() -> Collection<'static>
+{
+    Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        "LTR C 0002;Bill the Poney;3 w;Creature - Horse;C;",
+        "LTR R 0004;Boromir, Warden of the Tower;2 w;Legendary Creature - Human Soldier;R;",
+        "LTC R 0001;Banish from Edoras;4 w;Sorcery;R;",
+        ";Homemade card;1;Sorcery;C;",
+    ]).unwrap()
+}
+
+/// Fails for locations and tags which could not be saved
+pub(crate) fn check_label<'c>(label: &str) -> Result<(), CollectionError<'c>>
+{
+    match label.contains([';', '\n', '\r'])
+    {
+        true => Err(CollectionError::ReservedCharacter(label.to_string())),
+        false => Ok(()),
+    }
+}
+
+/// New count of a card once `quantity` copies are added to its stock, unless it would overflow
+fn room_for<'c>(stock: &Stock, quantity: usize) -> Result<usize, CollectionError<'c>>
+{
+    let owned = stock.total() ;
+    owned.checked_add(quantity).ok_or(CollectionError::TooManyCopies { owned, added: quantity })
+}
+
+impl<'c> From<ResolveError<'c>> for CollectionError<'c>
+{
+    fn from(value: ResolveError<'c>) -> Self
+    {
+        match value
+        {
+            ResolveError::NotFound => CollectionError::UnknownCard,
+            ResolveError::Ambiguous(candidates) => CollectionError::AmbiguousId(candidates),
+        }
+    }
+}
+
+impl std::fmt::Display for CollectionError<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            CollectionError::InvalidCard { index, reason } => write!(f, "Item #{} is not a card: {}", index, reason),
+            CollectionError::UnknownCard => write!(f, "No card of the collection matches this id"),
+            CollectionError::AmbiguousId(candidates) => write!(f, "Several cards match this id: {:?}", candidates),
+            CollectionError::InsufficientQuantity { owned, requested } => write!(f, "Cannot remove {} copies, only {} are owned", requested, owned),
+            CollectionError::TooManyCopies { owned, added } => write!(f, "Cannot add {} copies to the {} owned", added, owned),
+            CollectionError::ReservedCharacter(label) => write!(f, "`{}` cannot contain a `;` or a line break", label.escape_debug()),
+        }
+    }
+}
+
+impl std::error::Error for CollectionError<'_> {}
+
+/// Key under which a serial is indexed: `"LTR C 0001"` and `"LTR-1"` share the `"LTR 1"` key
+pub(crate) fn serial_key(serial: &str) -> String
+{
+    match crate::ids::parse_serial(serial)
+    {
+        Some((set, _, number)) => format!("{} {}", set, number),
+        None => crate::names::normalize(serial),
+    }
+}
+
+#[test]
+fn collection_collector_number_order()
+{
+    let mut collection = Collection::make(vec![
+        "LTR R 0004;Boromir;2 w;Sorcery;R;",
+        ";Homemade card;1;Sorcery;C;",
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        "LTC C 0002;Banish from Edoras;4 w;Sorcery;C;",
+        "LTR R 0002;The Battle of Bywater;1 w w;Sorcery;R;",
+        "LTC C 0001;Other card;4 w;Sorcery;C;",
+    ]).unwrap() ;
+
+    let serials: Vec<Option<&str>> = collection.cards.iter().map(|(card, _)| card.printing().serial()).collect() ;
+    assert_eq!(serials, vec![Some("LTR C 0001"), Some("LTR R 0002"), Some("LTR R 0004"), Some("LTC C 0001"), Some("LTC C 0002"), None]) ;
+
+    collection.add(Id::Serial("LTR-4"), 2).unwrap() ;
+    collection.add("homemade  card", 1).unwrap() ;
+    assert_eq!(collection.count("Boromir"), Some(2)) ;
+    assert_eq!(collection.count("Homemade card"), Some(1)) ;
+    assert_eq!(collection.count("LTC C 2"), Some(0)) ;
+    assert_eq!(collection.printings("Banish from Edoras").map(|printings| printings.len()), Ok(2)) ;
+}
+
+/// A linear scan would need 30,000 × 30,000 comparisons here, timed in `benches/bulk_import.rs`
+#[test]
+fn collection_bulk_import()
+{
+    let lines: Vec<String> = (1..=30_000)
+        .map(|number| format!("BLK C {:05};Bulk card {};1;Sorcery;C;", number, number))
+        .collect() ;
+
+    let mut collection = Collection::make(lines.iter().map(String::as_str).collect()).unwrap() ;
+    for number in 1..=30_000
+    {
+        collection.add(format!("BLK C {}", number).as_str(), 1).unwrap() ;
+        collection.add(format!("Bulk card {}", number).as_str(), 1).unwrap() ;
+    }
+
+    assert_eq!(collection.count("BLK C 12345"), Some(2)) ;
+    assert_eq!(collection.count("bulk card 30000"), Some(2)) ;
+}
+
+#[test]
+fn collection_fallible_operations()
+{
+    use crate::ids::Id::{*} ;
+
+    assert_eq!(
+        Collection::make(vec!["LTR C 0001;Banish from Edoras;4 w;Sorcery;C;", "LTR C 0002;Broken"]).err(),
+        Some(CollectionError::InvalidCard { index: 1, reason: "No Cost data found".to_string() })
+    ) ;
+
+    let mut collection = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        "LTR R 0002;The Battle of Bywater;1 w w;Sorcery;R;",
+    ]).unwrap() ;
+
+    assert_eq!(collection.add("Dawn of a New Age", 1), Err(CollectionError::UnknownCard)) ;
+    assert_eq!(collection.add("Banish from Edoras", 3), Ok(3)) ;
+    assert_eq!(collection.add(Both("Banish from Edoras", "LTR R 0002"), 1), Err(CollectionError::UnknownCard)) ;
+    assert!(matches!(collection.add(Either("Banish from Edoras", "LTR R 0002"), 1), Err(CollectionError::AmbiguousId(_)))) ;
+    assert_eq!(collection.add(Either("Banish from Edoras", "LTR C 0001"), 1), Ok(4)) ;
+
+    assert_eq!(collection.remove("LTR C 1", 5), Err(CollectionError::InsufficientQuantity { owned: 4, requested: 5 })) ;
+    assert_eq!(collection.count("Banish from Edoras"), Some(4)) ;
+    assert_eq!(collection.remove("LTR C 1", 3), Ok(1)) ;
+    assert_eq!(collection.remove("The Battle of Bywater", 0), Ok(0)) ;
+    assert_eq!(collection.remove("Dawn of a New Age", 1), Err(CollectionError::UnknownCard)) ;
+
+    assert_eq!(collection.add("Banish from Edoras", usize::MAX), Err(CollectionError::TooManyCopies { owned: 1, added: usize::MAX })) ;
+    assert_eq!(collection.add_all([("Banish from Edoras", usize::MAX - 1), ("LTR C 1", 1)]), Err(CollectionError::TooManyCopies { owned: usize::MAX, added: 1 })) ;
+    assert_eq!(collection.count("Banish from Edoras"), Some(1)) ;
+
+    collection.add("The Battle of Bywater", 2).unwrap() ;
+    assert_eq!(collection.saturating_remove("The Battle of Bywater", 10), Ok(0)) ;
+    assert_eq!(collection.saturating_remove("Dawn of a New Age", 1), Err(CollectionError::UnknownCard)) ;
+}
+
+#[test]
+fn collection_iterators()
+{
+    let catalog = crate::ltr::collection() ;
+    let mut collection = Collection::from_counts(&catalog, [("LTR M 0005", 1), ("Banish from Edoras", 3)]).unwrap() ;
+    assert_eq!(collection.len(), 5) ;
+    assert!(!collection.is_empty()) ;
+    assert_eq!(collection.total(), 4) ;
+    assert_eq!(catalog.total(), 0) ;
+
+    let names: Vec<&str> = collection.owned().map(|(card, _)| *card.name()).collect() ;
+    assert_eq!(names, vec!["Banish from Edoras", "Dawn of a New Age"]) ;
+    assert_eq!(collection.missing().count(), 3) ;
+    assert_eq!(collection.iter().len(), 5) ;
+    assert_eq!(collection.iter().next_back().map(|(_, count)| count), Some(1)) ;
+    assert_eq!((&collection).into_iter().map(|(_, count)| count).sum::<usize>(), 4) ;
+
+    assert!(matches!(Collection::from_counts(&catalog, [("Banish from Edoras", 1), ("Sauron", 1)]), Err(CollectionError::UnknownCard))) ;
+    assert_eq!(collection.add_all([("Banish from Edoras", 1), ("Sauron", 1)]), Err(CollectionError::UnknownCard)) ;
+    assert_eq!(collection.count("Banish from Edoras"), Some(3)) ;
+
+    // Extending with known and new printings
+    let reprint = GenericCard::try_from("LTC C 0010;Banish from Edoras;4 w;Sorcery;C;").unwrap() ;
+    let known = GenericCard::try_from(crate::ltr::BANISH_FROM_EDORAS).unwrap() ;
+    let custom = GenericCard::try_from(";Homemade card;1;Sorcery;C;").unwrap() ;
+    collection.extend([(reprint.clone(), 2), (known, 1), (custom.clone(), 1), (reprint, 1)]) ;
+    collection.extend([custom]) ;
+
+    assert_eq!(collection.len(), 7) ;
+    assert_eq!(collection.count("LTR C 0001"), Some(4)) ;
+    assert_eq!(collection.count("LTC C 0010"), Some(3)) ;
+    assert_eq!(collection.count("Homemade card"), Some(2)) ;
+    let serials: Vec<Option<&str>> = collection.iter().map(|(card, _)| card.printing().serial()).collect() ;
+    assert_eq!(serials[4..], [Some("LTR M 0005"), Some("LTC C 0010"), None]) ;
+
+    let owned: Vec<(GenericCard, usize)> = collection.into_iter().filter(|(_, count)| *count > 0).collect() ;
+    assert_eq!(owned.len(), 4) ;
+    let rebuilt: Collection = owned.into_iter().collect() ;
+    assert_eq!(rebuilt.total(), 10) ;
+}
+
+#[test]
+fn collection_variants()
+{
+    use crate::{Condition, Finish, Language} ;
+
+    let mut collection = Collection::from_counts(&crate::ltr::collection(), [("Banish from Edoras", 2)]).unwrap() ;
+    let foil = Variant::new(Finish::Foil, Condition::NearMint, Language::English) ;
+    let damaged = Variant::new(Finish::Nonfoil, Condition::Damaged, Language::Japanese) ;
+
+    assert_eq!(collection.add_variant("Banish from Edoras", foil, 1), Ok(3)) ;
+    assert_eq!(collection.add_variant("Banish from Edoras", damaged, 2), Ok(5)) ;
+    assert_eq!(collection.add_variant("Sauron", foil, 1), Err(CollectionError::UnknownCard)) ;
+    assert_eq!(collection.count("Banish from Edoras"), Some(5)) ;
+    assert_eq!(collection.count_variant("Banish from Edoras", &Variant::default()), Some(2)) ;
+    assert_eq!(collection.count_variant("Banish from Edoras", &foil), Some(1)) ;
+    assert_eq!(collection.count_variant("LTR R 0004", &foil), Some(0)) ;
+    assert_eq!(collection.total(), 5) ;
+
+    assert_eq!(collection.remove_variant("Banish from Edoras", &foil, 2), Err(CollectionError::InsufficientQuantity { owned: 1, requested: 2 })) ;
+    assert_eq!(collection.remove_variant("Banish from Edoras", &foil, 1), Ok(4)) ;
+    // Plain removals take the default variant first
+    assert_eq!(collection.remove("Banish from Edoras", 3), Ok(1)) ;
+    assert_eq!(collection.stock("Banish from Edoras").map(|stock| stock.iter().map(|(variant, count)| (*variant, count)).collect::<Vec<_>>()), Some(vec![(damaged, 1)])) ;
+
+    let mut other = collection.clone() ;
+    other.add_variant("Banish from Edoras", foil, 2).unwrap() ;
+    let union = collection.union(&other) ;
+    assert_eq!(union.count_variant("Banish from Edoras", &damaged), Some(2)) ;
+    assert_eq!(union.count_variant("Banish from Edoras", &foil), Some(2)) ;
+    assert_eq!(other.difference(&collection).stock("Banish from Edoras").map(Stock::total), Some(2)) ;
+    assert_eq!(other.intersection(&collection).count("Banish from Edoras"), Some(1)) ;
+    assert_eq!(collection.stocks().filter(|(_, stock)| !stock.is_empty()).count(), 1) ;
+
+    // Plain copies are in their printing's language
+    let mut japanese = Collection::make(vec!["LTR M 0005;Dawn of a New Age;1 w;Enchantment;M;;;ja"]).unwrap() ;
+    japanese.add("Dawn of a New Age", 2).unwrap() ;
+    assert_eq!(japanese.count_variant("Dawn of a New Age", &Variant { language: Language::Japanese, ..Variant::default() }), Some(2)) ;
+    assert_eq!(japanese.count_variant("Dawn of a New Age", &Variant::default()), Some(0)) ;
+}
+
+#[test]
+fn collection_locations_and_tags()
+{
+    let mut collection = Collection::from_counts(&crate::ltr::collection(), [("Banish from Edoras", 4), ("Bill the Poney", 1)]).unwrap() ;
+    let foil = Variant { finish: crate::Finish::Foil, ..Variant::default() } ;
+
+    assert_eq!(collection.add_at("LTR R 0004", foil, "Box: LTR bulk", 2), Ok(2)) ;
+    assert_eq!(collection.move_copies("Banish from Edoras", None, Some("Box: LTR bulk"), 3), Ok(3)) ;
+    assert_eq!(collection.move_copies("Banish from Edoras", None, Some("Box: LTR bulk"), 3), Err(CollectionError::InsufficientQuantity { owned: 1, requested: 3 })) ;
+    assert_eq!(collection.move_copies("Sauron", None, Some("Box: LTR bulk"), 1), Err(CollectionError::UnknownCard)) ;
+    assert_eq!(collection.move_copies("Banish from Edoras", Some("box: ltr bulk"), Some("in Boromir deck"), 1), Ok(1)) ;
+
+    assert_eq!(collection.total(), 7) ;
+    assert_eq!(collection.count("Banish from Edoras"), Some(4)) ;
+    assert_eq!(collection.locations(), vec!["Box: LTR bulk", "in Boromir deck"]) ;
+    let in_box: Vec<(&str, usize)> = collection.in_location(Some("Box: LTR bulk")).map(|(card, count)| (*card.name(), count)).collect() ;
+    assert_eq!(in_box, vec![("Banish from Edoras", 2), ("Boromir, Warden of the Tower", 2)]) ;
+    let unsorted: Vec<(&str, usize)> = collection.in_location(None).map(|(card, count)| (*card.name(), count)).collect() ;
+    assert_eq!(unsorted, vec![("Banish from Edoras", 1), ("Bill the Poney", 1)]) ;
+
+    assert_eq!(collection.tag("Banish from Edoras", "trade"), Ok(true)) ;
+    assert_eq!(collection.tag("Banish from Edoras", "Trade"), Ok(false)) ;
+    assert_eq!(collection.tag("Banish from Edoras", "trade;keep"), Err(CollectionError::ReservedCharacter("trade;keep".to_string()))) ;
+    assert!(collection.add_at("Banish from Edoras", foil, "Binder; page 2", 1).is_err()) ;
+    assert!(collection.move_copies("Banish from Edoras", None, Some("Binder; page 2"), 1).is_err()) ;
+    assert_eq!(collection.tag("Dawn of a New Age", "trade"), Ok(true)) ;
+    assert_eq!(collection.tag("Sauron", "trade"), Err(CollectionError::UnknownCard)) ;
+    let trade: Vec<(&str, usize)> = collection.tagged("trade").map(|(card, count)| (*card.name(), count)).collect() ;
+    assert_eq!(trade, vec![("Banish from Edoras", 4), ("Dawn of a New Age", 0)]) ;
+    assert_eq!(collection.untag("Dawn of a New Age", "TRADE"), Ok(true)) ;
+    assert_eq!(collection.tagged("trade").count(), 1) ;
+}
+
+#[test]
+fn collection_algebra()
+{
+    let catalog = crate::ltr::collection() ;
+    let mine = Collection::from_counts(&catalog, [("Banish from Edoras", 4), ("Bill the Poney", 1), ("Dawn of a New Age", 2)]).unwrap() ;
+
+    // Another source, with a reprint and name-only cards
+    let yours = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        ";Dawn of a New Age;1 w;Enchantment;M;",
+        "LTC R 0042;The Battle of Bywater;1 w w;Sorcery;R;",
+    ]).unwrap() ;
+    let yours = Collection::from_counts(&yours, [("Banish from Edoras", 1), ("Dawn of a New Age", 3), ("LTC R 42", 2)]).unwrap() ;
+
+    let union = mine.union(&yours) ;
+    assert_eq!(union.len(), 6) ;
+    assert_eq!(union.count("Banish from Edoras"), Some(5)) ;
+    assert_eq!(union.count("Dawn of a New Age"), Some(5)) ;
+    assert_eq!(union.count("LTC R 0042"), Some(2)) ;
+    assert_eq!(union.count("LTR R 0002"), Some(0)) ;
+    assert_eq!(union.total(), 13) ;
+
+    let difference = mine.difference(&yours) ;
+    assert_eq!(difference.len(), 5) ;
+    assert_eq!(difference.count("Banish from Edoras"), Some(3)) ;
+    assert_eq!(difference.count("Dawn of a New Age"), Some(0)) ;
+    assert_eq!(difference.count("Bill the Poney"), Some(1)) ;
+
+    let intersection = mine.intersection(&yours) ;
+    assert_eq!(intersection.count("Banish from Edoras"), Some(1)) ;
+    assert_eq!(intersection.count("Dawn of a New Age"), Some(2)) ;
+    assert_eq!(intersection.count("Bill the Poney"), Some(0)) ;
+    assert_eq!(intersection.total(), 3) ;
+
+    let mut later = mine.clone() ;
+    later.remove("Bill the Poney", 1).unwrap() ;
+    later.add("Banish from Edoras", 2).unwrap() ;
+    later.add("LTR R 0004", 1).unwrap() ;
+
+    let diff = mine.diff(&later) ;
+    assert_eq!(diff.changes.len(), 3) ;
+    assert_eq!(diff.added().map(|change| *change.card.name()).collect::<Vec<_>>(), vec!["Boromir, Warden of the Tower"]) ;
+    assert_eq!(diff.removed().map(|change| change.delta()).collect::<Vec<_>>(), vec![-1]) ;
+    assert_eq!(diff.changed().map(|change| (change.before, change.after)).collect::<Vec<_>>(), vec![(4, 6)]) ;
+    assert_eq!(
+        diff.to_string(),
+        "+2 Banish from Edoras (LTR C 0001): 4 -> 6 (changed)\n\
+         -1 Bill the Poney (LTR U 0003): 1 -> 0 (removed)\n\
+         +1 Boromir, Warden of the Tower (LTR R 0004): 0 -> 1 (added)\n\
+         3 cards changed: 3 copies gained, 1 copies lost\n"
+    ) ;
+
+    assert!(mine.diff(&mine).is_empty()) ;
+    assert_eq!(mine.diff(&mine).to_string(), "No changes\n") ;
+
+    let new_source = mine.diff(&yours) ;
+    assert_eq!(new_source.added().map(|change| change.card.printing().serial()).collect::<Vec<_>>(), vec![Some("LTC R 0042")]) ;
+}
+
+/// Serialized as a list of `{"card": {...}, "count": 2}` entries,
+/// along with `"variants": [{"finish": "foil", "condition": "NM", "language": "en", "location": "Binder 1", "count": 1}, ...]`
+/// when some copies are not unsorted copies of the default variant, and `"tags": [...]` when the card has tags
+#[cfg(feature = "serde")]
+impl serde::Serialize for Collection<'_>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        use serde::ser::SerializeSeq ;
+
+        let mut seq = serializer.serialize_seq(Some(self.cards.len()))? ;
+        for (card, stock) in &self.cards
+        {
+            let variants: Vec<VariantEntry> = stock.lots()
+                .map(|lot| VariantEntry { variant: lot.variant, location: lot.location.clone(), count: lot.count })
+                .collect() ;
+            let default_only = stock.lots().all(|lot| lot.variant == Variant::of(card.printing()) && lot.location.is_none()) ;
+            seq.serialize_element(&Entry
+            {
+                card: card.clone(),
+                count: stock.total(),
+                variants: if default_only { vec![] } else { variants },
+                tags: stock.tags().map(str::to_string).collect(),
+            })? ;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Entry<C>
+{
+    card: C,
+    #[serde(default)]
+    count: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variants: Vec<VariantEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl<C> Entry<C>
+{
+    /// Copies not covered by `variants` are of the card's default variant
+    fn stock(&self, default: Variant) -> Stock
+    {
+        let mut stock = Stock::default() ;
+        for variant in &self.variants
+        {
+            stock.add_at(variant.variant, variant.location.as_deref(), variant.count) ;
+        }
+        let rest = self.count.saturating_sub(stock.total()) ;
+        stock.add(default, rest) ;
+        for tag in &self.tags
+        {
+            stock.tag(tag) ;
+        }
+        stock
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VariantEntry
+{
+    #[serde(flatten)]
+    variant: Variant,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+    count: usize,
+}
+
+/// Cards borrow their strings from the input, and the default policy is used<br/>
+/// Use [`Collection::deserialize_in`] for inputs with escaped strings or read from a reader
+#[cfg(feature = "serde")]
+impl<'de: 'gc, 'gc> serde::Deserialize<'de> for Collection<'gc>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let entries = <Vec<Entry<GenericCard<'gc>>> as serde::Deserialize>::deserialize(deserializer)? ;
+        Ok(Collection::from_entries(entries.into_iter().map(|entry| {
+            let stock = entry.stock(Variant::of(entry.card.printing())) ;
+            (entry.card, stock)
+        }).collect()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'gc> Collection<'gc>
+{
+    /// Reads a serialized collection without borrowing from the input, its cards being resolved against a catalog<br/>
+    /// Every card of the catalog is kept, and a card which does not resolve to exactly one card of the catalog is an error
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::ltr;
+    ///
+    /// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 2)]).unwrap() ;
+    /// let json = serde_json::to_vec(&collection).unwrap() ;
+    ///
+    /// let catalog = ltr::collection() ;
+    /// let read = Collection::deserialize_in(&catalog, &mut serde_json::Deserializer::from_reader(json.as_slice())).unwrap() ;
+    /// assert_eq!(read.count("LTR C 0001"), Some(2)) ;
+    /// ```
+    pub fn deserialize_in<'de, D: serde::Deserializer<'de>>(catalog: &Collection<'gc>, deserializer: D) -> Result<Collection<'gc>, D::Error>
+    {
+        use serde::de::Error ;
+
+        let entries = <Vec<Entry<crate::cards::OwnedCard>> as serde::Deserialize>::deserialize(deserializer)? ;
+        let mut collection = Collection::from_counts(catalog, std::iter::empty::<(&str, usize)>())
+            .expect("An empty list of counts always resolves") ;
+        for entry in entries
+        {
+            let index = collection.position(&entry.card, &catalog.policy).map_err(D::Error::custom)? ;
+            let (card, stock) = &mut collection.cards[index] ;
+            stock.merge(&entry.stock(Variant::of(card.printing()))) ;
+        }
+        Ok(collection)
     }
 }
 
-pub struct Deck<'a>([Id<'a> ; 60]) ;
\ No newline at end of file
+#[cfg(feature = "serde")]
+#[test]
+fn collection_to_json()
+{
+    let mut collection = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.",
+        r#"LTR R 0004;"Boromir, Warden of the Tower";2 w;Legendary Creature - Human Soldier;R;Vigilence"#,
+        "LTR M 0005;Dawn of a New Age;1 w;Enchantment;M;;;ja",
+    ]).unwrap() ;
+    collection.add("Banish from Edoras", 3).unwrap() ;
+    collection.add("LTR R 0004", 1).unwrap() ;
+
+    let json = serde_json::to_string(&collection).unwrap() ;
+    assert!(json.starts_with(r#"[{"card":{"serial":"LTR C 0001","name":"Banish from Edoras","cost":"{4}{W}","kind":"Sorcery","rarity":"C","effects":"Exile target creature."},"count":3},"#)) ;
+    assert!(json.ends_with(r#""effects":"","language":"ja"},"count":0}]"#)) ;
+
+    let back: Collection = serde_json::from_str(&json).unwrap() ;
+    assert_eq!(back.count("Banish from Edoras"), Some(3)) ;
+    assert_eq!(back.count("Boromir, Warden of the Tower"), Some(1)) ;
+    assert_eq!(back.count("Dawn of a New Age"), Some(0)) ;
+    assert_eq!(serde_json::to_string(&back).unwrap(), json) ;
+
+    let foil = Variant { finish: crate::Finish::Foil, ..Variant::default() } ;
+    collection.add_variant("LTR R 0004", foil, 2).unwrap() ;
+    let json = serde_json::to_string(&collection).unwrap() ;
+    assert!(json.contains(r#""count":3,"variants":[{"finish":"nonfoil","condition":"NM","language":"en","count":1},{"finish":"foil","condition":"NM","language":"en","count":2}]"#)) ;
+    let back: Collection = serde_json::from_str(&json).unwrap() ;
+    assert_eq!(back.stock("LTR R 0004"), collection.stock("LTR R 0004")) ;
+
+    collection.move_copies("LTR R 0004", None, Some("Binder 1"), 1).unwrap() ;
+    collection.tag("LTR R 0004", "trade").unwrap() ;
+    let json = serde_json::to_string(&collection).unwrap() ;
+    assert!(json.contains(r#"{"finish":"foil","condition":"NM","language":"en","location":"Binder 1","count":1}],"tags":["trade"]}"#)) ;
+    let back: Collection = serde_json::from_str(&json).unwrap() ;
+    assert_eq!(back.stock("LTR R 0004"), collection.stock("LTR R 0004")) ;
+
+    // Escaped strings cannot be borrowed, but can be resolved against a catalog
+    let escaped = json.replacen("Banish", "\\u0042anish", 1) ;
+    assert!(serde_json::from_str::<Collection>(&escaped).is_err()) ;
+    let read = Collection::deserialize_in(&collection, &mut serde_json::Deserializer::from_reader(escaped.as_bytes())).unwrap() ;
+    assert_eq!(read.stock("LTR R 0004"), collection.stock("LTR R 0004")) ;
+    assert_eq!(read.count("Banish from Edoras"), Some(3)) ;
+    assert!(Collection::deserialize_in(&collection, &mut serde_json::Deserializer::from_str(r#"[{"card":{"name":"Gandalf","cost":"{W}","kind":"Sorcery","rarity":"C","effects":""},"count":1}]"#)).is_err()) ;
+}
+
+#[test]
+fn collection_resolve()
+{
+    use crate::ids::Id::{*} ;
+
+    let mut collection = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.",
+        "LTR R 0002;The Battle of Bywater;1 w w;Sorcery;R;Destroy all creatures with power 3 or greater.",
+        "LTC C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.",
+    ]).unwrap() ;
+
+    assert_eq!(collection.resolve("LTR R 2", &Policy::Strict).map(|card| *card.name()), Ok("The Battle of Bywater")) ;
+    assert_eq!(collection.resolve("Dawn of a New Age", &Policy::Strict).err(), Some(ResolveError::NotFound)) ;
+    assert!(matches!(collection.resolve("Banish from Edoras", &Policy::Strict), Err(ResolveError::Ambiguous(candidates)) if candidates.len() == 2)) ;
+    assert_eq!(collection.resolve("Banish from Edoras", &Policy::PreferNewest).map(GenericCard::id), Ok(Serial("LTC C 0001"))) ;
+    assert_eq!(collection.resolve("Banish from Edoras", &Policy::PreferSet("LTR".to_string())).map(GenericCard::id), Ok(Serial("LTR C 0001"))) ;
+
+    // Ambiguous ids are left untouched by the default strict policy
+    assert!(matches!(collection.add("Banish from Edoras", 2), Err(CollectionError::AmbiguousId(candidates)) if candidates.len() == 2)) ;
+    assert_eq!(collection.count("LTR C 0001"), Some(0)) ;
+    assert_eq!(collection.count("Banish from Edoras"), None) ;
+
+    let mut collection = collection.with_policy(Policy::PreferSet("LTC".to_string())) ;
+    assert_eq!(collection.add("Banish from Edoras", 2), Ok(2)) ;
+    assert_eq!(collection.count("LTR C 0001"), Some(0)) ;
+    assert_eq!(collection.count("LTC C 0001"), Some(2)) ;
+}
+
+#[test]
+fn collection_oracle()
+{
+    let collection = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;Exile target creature.",
+        "LTR R 0002;The Battle of Bywater;1 w w;Sorcery;R;Destroy all creatures with power 3 or greater.",
+        "LTC U 0001;Banish from Edoras;4 w;Sorcery;U;Exile target creature.;John Doe;fr",
+    ]).unwrap() ;
+
+    let oracle = collection.oracle("banish from edoras").unwrap() ;
+    assert_eq!(*oracle.name(), "Banish from Edoras") ;
+    assert_eq!(collection.oracle("LTC U 1").map(|oracle| *oracle.name()), Ok("Banish from Edoras")) ;
+    assert_eq!(collection.oracle("Dawn of a New Age").err(), Some(ResolveError::NotFound)) ;
+    assert!(collection.oracle(Id::Either("The Battle of Bywater", "LTR C 0001")).is_err()) ;
+
+    let printings = collection.printings("LTR C 0001").unwrap() ;
+    assert_eq!(printings.len(), 2) ;
+    assert_eq!(printings[0].rarity(), &crate::Rarity::Common) ;
+    assert_eq!(printings[1].printing().language(), &crate::Language::French) ;
+    assert_eq!(printings[1].printing().set(), Some("LTC".to_string())) ;
+    // One oracle record, shared by its printings and by the collections made out of this one
+    assert!(printings[0].shares_oracle(printings[1])) ;
+    assert!(std::ptr::eq(oracle, printings[1].oracle())) ;
+    let owned = Collection::from_counts(&collection, [("LTC U 1", 1)]).unwrap() ;
+    assert!(owned.resolve("LTC U 1", owned.policy()).unwrap().shares_oracle(printings[0])) ;
+
+    assert_eq!(collection.printings("The Battle of Bywater").map(|printings| printings.len()), Ok(1)) ;
+}
@@ -0,0 +1,222 @@
+use crate::cards::GenericCard;
+use crate::collections::Collection;
+use crate::Rarity;
+
+/// Number of copies making a playset
+pub const PLAYSET: usize = 4 ;
+
+/// Rarities, in the order they are reported in
+const RARITIES: [Rarity ; 4] = [Rarity::Common, Rarity::Uncommon, Rarity::Rare, Rarity::Mythical] ;
+
+/// Completion of a collection against a reference catalog (such as `ltr::collection()`), per set and per rarity<br/>
+/// Cards of the collection are matched to the catalog by printing, or by id (see [`Collection::union`])
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::{completion, ltr, Rarity};
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4), ("LTR R 0004", 1)]).unwrap() ;
+/// let report = completion::report(&collection, &ltr::collection()) ;
+///
+/// let ltr = report.set("LTR").unwrap() ;
+/// assert_eq!((ltr.overall.owned, ltr.overall.total, ltr.overall.playsets), (2, 5, 1)) ;
+/// assert_eq!(ltr.rarity(Rarity::Common).unwrap().percent(), 100.0) ;
+/// assert_eq!(ltr.rarity(Rarity::Rare).unwrap().percent(), 50.0) ;
+/// ```
+pub fn report<'gc>(collection: &Collection<'gc>, reference: &Collection<'gc>) -> CompletionReport<'gc>
+{
+    let (stocks, _) = reference.match_stocks(collection) ;
+    let mut sets: Vec<SetCompletion<'gc>> = vec![] ;
+
+    for ((card, _), stock) in reference.iter().zip(stocks)
+    {
+        let owned = stock.total() ;
+        let set = card.printing().set() ;
+        let position = match sets.iter().position(|known| known.set == set)
+        {
+            Some(position) => position,
+            None => {
+                sets.push(SetCompletion { set, overall: Completion::default(), rarities: vec![] }) ;
+                sets.len() - 1
+            },
+        } ;
+
+        let completion = &mut sets[position] ;
+        completion.overall.record(card, owned) ;
+        match completion.rarities.iter_mut().find(|(rarity, _)| rarity == card.rarity())
+        {
+            Some((_, by_rarity)) => by_rarity.record(card, owned),
+            None => {
+                let mut by_rarity = Completion::default() ;
+                by_rarity.record(card, owned) ;
+                completion.rarities.push((*card.rarity(), by_rarity)) ;
+            },
+        }
+    }
+
+    for set in &mut sets
+    {
+        set.rarities.sort_by_key(|(rarity, _)| RARITIES.iter().position(|known| known == rarity)) ;
+    }
+
+    CompletionReport { sets }
+}
+
+/// Completion of each set of the reference catalog, in catalog order
+#[derive(Debug, Clone)]
+pub struct CompletionReport<'gc>
+{
+    pub sets: Vec<SetCompletion<'gc>>,
+}
+
+impl<'gc> CompletionReport<'gc>
+{
+    /// Completion of a set, by set code (such as `"LTR"`)
+    pub fn set(&self, code: &str) -> Option<&SetCompletion<'gc>>
+    {
+        self.sets.iter().find(|set| set.set.as_deref().is_some_and(|set| set.eq_ignore_ascii_case(code.trim())))
+    }
+}
+
+/// Completion of a set, as a whole and per rarity
+#[derive(Debug, Clone)]
+pub struct SetCompletion<'gc>
+{
+    /// Set code, `None` for the catalog's cards without serial
+    pub set: Option<String>,
+    pub overall: Completion<'gc>,
+    /// From commons to mythics, only for the rarities found in the set
+    pub rarities: Vec<(Rarity, Completion<'gc>)>,
+}
+
+impl<'gc> SetCompletion<'gc>
+{
+    pub fn rarity(&self, rarity: Rarity) -> Option<&Completion<'gc>>
+    {
+        self.rarities.iter().find(|(known, _)| *known == rarity).map(|(_, completion)| completion)
+    }
+}
+
+/// Completion of a group of cards
+#[derive(Debug, Clone, Default)]
+pub struct Completion<'gc>
+{
+    /// Number of different cards in the group
+    pub total: usize,
+    /// Number of different cards owned at least once
+    pub owned: usize,
+    /// Number of cards owned at least [`PLAYSET`] times
+    pub playsets: usize,
+    /// Cards not owned at all, in collector-number order
+    pub missing: Vec<GenericCard<'gc>>,
+    /// Owned cards missing copies for a playset, along with the number of missing copies
+    pub incomplete_playsets: Vec<(GenericCard<'gc>, usize)>,
+}
+
+impl<'gc> Completion<'gc>
+{
+    fn record(&mut self, card: &GenericCard<'gc>, owned: usize)
+    {
+        self.total += 1 ;
+        match owned
+        {
+            0 => self.missing.push(card.clone()),
+            owned if owned < PLAYSET => self.incomplete_playsets.push((card.clone(), PLAYSET - owned)),
+            _ => self.playsets += 1,
+        }
+        if owned > 0 { self.owned += 1 ; }
+    }
+
+    /// Percentage of the different cards owned at least once
+    pub fn percent(&self) -> f64
+    {
+        percent(self.owned, self.total)
+    }
+
+    /// Percentage of the different cards owned at least [`PLAYSET`] times
+    pub fn playset_percent(&self) -> f64
+    {
+        percent(self.playsets, self.total)
+    }
+
+    /// Number of copies to get to complete every playset
+    pub fn missing_copies(&self) -> usize
+    {
+        self.missing.len() * PLAYSET + self.incomplete_playsets.iter().map(|(_, missing)| missing).sum::<usize>()
+    }
+}
+
+fn percent(part: usize, total: usize) -> f64
+{
+    if total == 0 { return 100.0 ; }
+    part as f64 * 100.0 / total as f64
+}
+
+/// One line per set, followed by one line per rarity, such as
+/// `LTR: 212/281 cards (75%), 3 playsets (1%)`
+impl std::fmt::Display for CompletionReport<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        for set in &self.sets
+        {
+            writeln!(f, "{}: {}", set.set.as_deref().unwrap_or("(no set)"), set.overall)? ;
+            for (rarity, completion) in &set.rarities
+            {
+                writeln!(f, "  {}: {}", rarity.name(), completion)? ;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Completion<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}/{} cards ({:.0}%), {} playsets ({:.0}%)", self.owned, self.total, self.percent(), self.playsets, self.playset_percent())
+    }
+}
+
+#[test]
+fn completion_report()
+{
+    let reference = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        "LTR C 0002;Bill the Poney;3 w;Creature - Horse;C;",
+        "LTR M 0003;Dawn of a New Age;1 w;Enchantment;M;",
+        "LTC R 0001;Banish from Edoras;4 w;Sorcery;R;",
+        ";Homemade card;1;Sorcery;C;",
+    ]).unwrap() ;
+    // Another source, matched by serial or by name
+    let collection = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        ";Bill the Poney;3 w;Creature - Horse;C;",
+        "LTC R 0001;Banish from Edoras;4 w;Sorcery;R;",
+    ]).unwrap() ;
+    let collection = Collection::from_counts(&collection, [("LTR C 0001", 5), ("Bill the Poney", 2), ("LTC R 0001", 4)]).unwrap() ;
+
+    let report = report(&collection, &reference) ;
+    assert_eq!(report.sets.iter().map(|set| set.set.as_deref()).collect::<Vec<_>>(), vec![Some("LTR"), Some("LTC"), None]) ;
+
+    let ltr = report.set("ltr").unwrap() ;
+    assert_eq!((ltr.overall.owned, ltr.overall.total, ltr.overall.playsets), (2, 3, 1)) ;
+    assert_eq!(ltr.overall.missing.iter().map(|card| *card.name()).collect::<Vec<_>>(), vec!["Dawn of a New Age"]) ;
+    assert_eq!(ltr.overall.incomplete_playsets.iter().map(|(card, missing)| (*card.name(), *missing)).collect::<Vec<_>>(), vec![("Bill the Poney", 2)]) ;
+    assert_eq!(ltr.overall.missing_copies(), 6) ;
+    assert_eq!(ltr.rarities.iter().map(|(rarity, _)| *rarity).collect::<Vec<_>>(), vec![Rarity::Common, Rarity::Mythical]) ;
+    assert_eq!(ltr.rarity(Rarity::Common).unwrap().percent(), 100.0) ;
+    assert_eq!(ltr.rarity(Rarity::Common).unwrap().playset_percent(), 50.0) ;
+    assert_eq!(ltr.rarity(Rarity::Mythical).unwrap().percent(), 0.0) ;
+    assert!(ltr.rarity(Rarity::Rare).is_none()) ;
+
+    assert_eq!(
+        report.to_string(),
+        "LTR: 2/3 cards (67%), 1 playsets (33%)\n\
+         \x20 Common: 2/2 cards (100%), 1 playsets (50%)\n\
+         \x20 Mythic: 0/1 cards (0%), 0 playsets (0%)\n\
+         LTC: 1/1 cards (100%), 1 playsets (100%)\n\
+         \x20 Rare: 1/1 cards (100%), 1 playsets (100%)\n\
+         (no set): 0/1 cards (0%), 0 playsets (0%)\n\
+         \x20 Common: 0/1 cards (0%), 0 playsets (0%)\n"
+    ) ;
+}
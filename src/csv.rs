@@ -0,0 +1,97 @@
+/// A record of a csv file, along with the line it starts on (starting at 1)
+pub(crate) type Record = (usize, Vec<String>) ;
+
+/// Splits a csv text into records<br/>
+/// Fields may be quoted (`"Boromir, Warden of the Tower"`), quotes being doubled inside them,
+/// and quoted fields may span several lines. Blank lines are skipped.
+pub(crate) fn records(text: &str, delimiter: char) -> Result<Vec<Record>, (usize, &'static str)>
+{
+    let text = text.strip_prefix('\u{FEFF}').unwrap_or(text) ;
+    let mut records = vec![] ;
+
+    let mut fields: Vec<String> = vec![] ;
+    let mut field = String::new() ;
+    let mut quoted = false ;
+    let mut line = 1 ;
+    let mut start = 1 ;
+
+    let mut chars = text.chars().peekable() ;
+    while let Some(c) = chars.next()
+    {
+        if quoted
+        {
+            match c
+            {
+                '"' if chars.peek() == Some(&'"') => { chars.next() ; field.push('"') ; },
+                '"' => quoted = false,
+                '\n' => { line += 1 ; field.push(c) ; },
+                _ => field.push(c),
+            }
+            continue ;
+        }
+
+        match c
+        {
+            '"' if field.trim().is_empty() => { field.clear() ; quoted = true ; },
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                fields.push(std::mem::take(&mut field)) ;
+                push_record(&mut records, start, std::mem::take(&mut fields)) ;
+                line += 1 ;
+                start = line ;
+            },
+            c if c == delimiter => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if quoted { return Err((start, "Unclosed quoted field")) ; }
+
+    fields.push(field) ;
+    push_record(&mut records, start, fields) ;
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<Record>, line: usize, fields: Vec<String>)
+{
+    if fields.iter().all(|field| field.trim().is_empty()) { return ; }
+    records.push((line, fields.into_iter().map(|field| field.trim().to_string()).collect())) ;
+}
+
+/// Quotes a field if it contains the delimiter, quotes or line breaks
+pub(crate) fn field(value: &str, delimiter: char) -> std::borrow::Cow<'_, str>
+{
+    if value.contains([delimiter, '"', '\n', '\r'])
+    {
+        std::borrow::Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        std::borrow::Cow::Borrowed(value)
+    }
+}
+
+#[test]
+fn csv_records()
+{
+    let text = "\u{FEFF}Count,Name,Edition\r\n2,\"Boromir, Warden of the Tower\",ltr\n\n1,\"The \"\"One\"\" Ring\",ltr\n3,\"Multi\nline\",\n" ;
+    assert_eq!(records(text, ',').unwrap(), vec![
+        (1, vec!["Count".to_string(), "Name".to_string(), "Edition".to_string()]),
+        (2, vec!["2".to_string(), "Boromir, Warden of the Tower".to_string(), "ltr".to_string()]),
+        (4, vec!["1".to_string(), "The \"One\" Ring".to_string(), "ltr".to_string()]),
+        (5, vec!["3".to_string(), "Multi\nline".to_string(), "".to_string()]),
+    ]) ;
+
+    assert_eq!(records("a;b", ';').unwrap(), vec![(1, vec!["a".to_string(), "b".to_string()])]) ;
+    assert_eq!(records("1,\"Unclosed", ','), Err((1, "Unclosed quoted field"))) ;
+}
+
+#[test]
+fn csv_field()
+{
+    assert_eq!(field("Banish from Edoras", ','), "Banish from Edoras") ;
+    assert_eq!(field("Boromir, Warden of the Tower", ','), "\"Boromir, Warden of the Tower\"") ;
+    assert_eq!(field("Boromir, Warden of the Tower", ';'), "Boromir, Warden of the Tower") ;
+    assert_eq!(field("The \"One\" Ring", ','), "\"The \"\"One\"\" Ring\"") ;
+
+    let line = ["Multi\nline", "a,b"].map(|value| field(value, ',')).join(",") ;
+    assert_eq!(records(&line, ',').unwrap(), vec![(1, vec!["Multi\nline".to_string(), "a,b".to_string()])]) ;
+}
@@ -0,0 +1,174 @@
+/// A point in time, in seconds since 1970-01-01 00:00:00 UTC<br/>
+/// Written as `2026-01-01T12:30:00Z`, and read from `2026-01-01`, `2026-01-01 12:30`, `2026-01-01 12:30:00`
+/// or `2026-01-01T12:30:00Z` (always in UTC)
+/// ```
+/// use mtg_multitool::dates::Timestamp;
+///
+/// let new_year = Timestamp::from_date(2026, 1, 1).unwrap() ;
+/// assert_eq!(Timestamp::try_from("2026-01-01"), Ok(new_year)) ;
+/// assert_eq!(Timestamp::try_from("2026-01-01 12:30"), Ok(new_year.plus(12 * 3600 + 30 * 60))) ;
+/// assert_eq!(new_year.to_string(), "2026-01-01T00:00:00Z") ;
+/// assert_eq!(new_year.date(), (2026, 1, 1)) ;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Timestamp(u64) ;
+
+const DAY: u64 = 24 * 3600 ;
+
+impl Timestamp
+{
+    pub fn from_seconds(seconds: u64) -> Timestamp
+    {
+        Timestamp(seconds)
+    }
+
+    /// Midnight (UTC) of a day, `None` if the date does not exist or is before 1970
+    pub fn from_date(year: u32, month: u32, day: u32) -> Option<Timestamp>
+    {
+        if year < 1970 || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) { return None ; }
+        Some(Timestamp(days_from_civil(year, month, day) * DAY))
+    }
+
+    /// Current time, according to the system clock
+    pub fn now() -> Timestamp
+    {
+        let elapsed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default() ;
+        Timestamp(elapsed.as_secs())
+    }
+
+    pub fn seconds(&self) -> u64
+    {
+        self.0
+    }
+
+    /// The timestamp, a number of seconds later
+    pub fn plus(&self, seconds: u64) -> Timestamp
+    {
+        Timestamp(self.0.saturating_add(seconds))
+    }
+
+    /// Day of the timestamp, as `(year, month, day)`
+    pub fn date(&self) -> (u32, u32, u32)
+    {
+        civil_from_days(self.0 / DAY)
+    }
+
+    /// Day of the timestamp, written `2026-01-01`
+    pub fn date_string(&self) -> String
+    {
+        let (year, month, day) = self.date() ;
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+fn is_leap_year(year: u32) -> bool
+{
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32
+{
+    match month
+    {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days from 1970-01-01 to a date, after Howard Hinnant's `days_from_civil`
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64
+{
+    let year = u64::from(if month <= 2 { year - 1 } else { year }) ;
+    let era = year / 400 ;
+    let year_of_era = year - era * 400 ;
+    let day_of_year = (153 * ((month as u64 + 9) % 12) + 2) / 5 + day as u64 - 1 ;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year ;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date of a number of days since 1970-01-01, after Howard Hinnant's `civil_from_days`
+fn civil_from_days(days: u64) -> (u32, u32, u32)
+{
+    let days = days + 719_468 ;
+    let era = days / 146_097 ;
+    let day_of_era = days - era * 146_097 ;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365 ;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100) ;
+    let shifted_month = (5 * day_of_year + 2) / 153 ;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1 ;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } ;
+    let year = year_of_era + era * 400 + u64::from(month <= 2) ;
+    (year as u32, month as u32, day as u32)
+}
+
+impl std::fmt::Display for Timestamp
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let time = self.0 % DAY ;
+        write!(f, "{}T{:02}:{:02}:{:02}Z", self.date_string(), time / 3600, time % 3600 / 60, time % 60)
+    }
+}
+
+impl TryFrom<&str> for Timestamp
+{
+    type Error = &'static str ;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error>
+    {
+        let value = value.trim() ;
+        let value = value.strip_suffix('Z').unwrap_or(value) ;
+        let (date, time) = match value.split_once(['T', ' '])
+        {
+            Some((date, time)) => (date, Some(time.trim())),
+            None => (value, None),
+        } ;
+
+        let mut fields = date.split('-').map(|field| field.parse::<u32>().ok()) ;
+        let day = match (fields.next(), fields.next(), fields.next(), fields.next())
+        {
+            (Some(Some(year)), Some(Some(month)), Some(Some(day)), None) => Timestamp::from_date(year, month, day).ok_or("Invalid date")?,
+            _ => return Err("Expected a `YYYY-MM-DD` date"),
+        } ;
+
+        let seconds = match time
+        {
+            None => 0,
+            Some(time) => {
+                let fields = time.split(':').map(|field| field.parse::<u64>().ok()).collect::<Vec<_>>() ;
+                match fields.as_slice()
+                {
+                    [Some(hours), Some(minutes)] if *hours < 24 && *minutes < 60 => hours * 3600 + minutes * 60,
+                    [Some(hours), Some(minutes), Some(seconds)] if *hours < 24 && *minutes < 60 && *seconds < 60 => hours * 3600 + minutes * 60 + seconds,
+                    _ => return Err("Expected a `HH:MM` or `HH:MM:SS` time"),
+                }
+            },
+        } ;
+
+        Ok(day.plus(seconds))
+    }
+}
+
+#[test]
+fn timestamp_calendar()
+{
+    assert_eq!(Timestamp::from_date(1970, 1, 1), Some(Timestamp(0))) ;
+    assert_eq!(Timestamp::from_date(2000, 3, 1).map(|time| time.seconds()), Some(951_868_800)) ;
+    assert_eq!(Timestamp::from_date(2024, 2, 29).map(|time| time.date()), Some((2024, 2, 29))) ;
+    assert_eq!(Timestamp::from_date(2026, 2, 29), None) ;
+    assert_eq!(Timestamp::from_date(1969, 12, 31), None) ;
+
+    let time = Timestamp::try_from("2026-12-31T23:59:59Z").unwrap() ;
+    assert_eq!(time.to_string(), "2026-12-31T23:59:59Z") ;
+    assert_eq!(time.plus(1).to_string(), "2027-01-01T00:00:00Z") ;
+    assert_eq!(Timestamp::try_from(time.to_string().as_str()), Ok(time)) ;
+
+    assert!(Timestamp::try_from("2026-13-01").is_err()) ;
+    assert!(Timestamp::try_from("2026-01-01 24:00").is_err()) ;
+    assert!(Timestamp::try_from("01/01/2026").is_err()) ;
+    assert!(Timestamp::now() > Timestamp::from_date(2024, 1, 1).unwrap()) ;
+}
@@ -0,0 +1,326 @@
+use crate::cards::GenericCard;
+use crate::collections::{Collection, CollectionError};
+use crate::ids::AsId;
+use crate::imports::{import, Format, ImportReport};
+use crate::loans::{Direction, LoanLedger};
+use crate::names::same_name;
+
+/// A deck: the copies of each printing it is built with
+#[derive(Debug, Clone)]
+pub struct Deck<'gc>
+{
+    pub name: String,
+    /// In the order they were added
+    cards: Vec<(GenericCard<'gc>, usize)>,
+}
+
+impl<'gc> Deck<'gc>
+{
+    pub fn new(name: &str) -> Deck<'gc>
+    {
+        Deck { name: name.trim().to_string(), cards: vec![] }
+    }
+
+    /// Adds copies of a card of a collection (such as the collection the deck is built from), returning its count in the deck
+    pub fn add<'any, T>(&mut self, collection: &Collection<'gc>, id: T, count: usize) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let card = collection.resolve(id, collection.policy())? ;
+        Ok(self.push(card.clone(), count))
+    }
+
+    fn push(&mut self, card: GenericCard<'gc>, count: usize) -> usize
+    {
+        match self.cards.iter_mut().find(|(known, _)| known.same_printing(&card))
+        {
+            Some((_, known)) => {
+                *known = known.saturating_add(count) ;
+                *known
+            },
+            None => {
+                self.cards.push((card, count)) ;
+                count
+            },
+        }
+    }
+
+    /// Reads a decklist in MTG Arena text (see [`Format::Arena`]), main deck and sideboard together<br/>
+    /// Lines which do not resolve to a card of the collection are left out, and listed in the report
+    pub fn from_decklist(collection: &Collection<'gc>, name: &str, text: &str) -> (Deck<'gc>, ImportReport<'gc>)
+    {
+        let (cards, report) = import(collection, Format::Arena, text).expect("Arena text is read line by line, without failing") ;
+        let mut deck = Deck::new(name) ;
+        for (card, count) in cards.owned()
+        {
+            deck.push(card.clone(), count) ;
+        }
+        (deck, report)
+    }
+
+    /// Cards of the deck and their counts, in the order they were added
+    pub fn cards(&self) -> impl Iterator<Item = (&GenericCard<'gc>, usize)>
+    {
+        self.cards.iter().map(|(card, count)| (card, *count))
+    }
+
+    /// Number of copies of a printing in the deck
+    pub fn count(&self, card: &GenericCard<'_>) -> usize
+    {
+        self.cards.iter()
+            .filter(|(known, _)| known.same_printing(card))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Total number of cards in the deck
+    pub fn total(&self) -> usize
+    {
+        self.cards.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Built decks, each reserving the copies it is built with from a collection<br/>
+/// Reserved copies still count as owned, but are not available for another deck nor for a loan (see [`Decks::available`])
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::decks::{Deck, Decks};
+/// use mtg_multitool::loans::LoanLedger;
+/// use mtg_multitool::ltr;
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4)]).unwrap() ;
+/// let (boromir, _) = Deck::from_decklist(&collection, "Boromir", "3 Banish from Edoras (LTR) 1") ;
+/// let (gondor, _) = Deck::from_decklist(&collection, "Gondor", "2 Banish from Edoras (LTR) 1") ;
+///
+/// let mut decks = Decks::default() ;
+/// let loans = LoanLedger::default() ;
+/// assert!(decks.build(&collection, &loans, boromir).unwrap().is_empty()) ;
+/// let conflicts = decks.build(&collection, &loans, gondor).unwrap() ;
+/// assert_eq!(conflicts[0].to_string(), "Banish from Edoras (LTR C 0001): 4 owned, 5 needed by Boromir (3), Gondor (2)") ;
+/// assert!(decks.build(&collection, &loans, Deck::new("BOROMIR")).is_err()) ;
+///
+/// decks.disassemble("gondor").unwrap() ;
+/// assert_eq!(decks.available(&collection, &loans, "Banish from Edoras"), Ok(1)) ;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Decks<'gc>
+{
+    decks: Vec<Deck<'gc>>,
+}
+
+impl<'gc> Decks<'gc>
+{
+    /// Builds a deck, reserving its copies, and returns the conflicts it is part of<br/>
+    /// The deck is built even if there are not enough copies for it: the conflicts say which copies are missing.
+    /// Fails if a deck of the same name is already built
+    pub fn build(&mut self, collection: &Collection<'gc>, loans: &LoanLedger<'gc>, deck: Deck<'gc>) -> Result<Vec<Conflict<'gc>>, &'static str>
+    {
+        if self.get(&deck.name).is_some()
+        {
+            return Err("A deck of that name is already built") ;
+        }
+
+        let name = deck.name.clone() ;
+        self.decks.push(deck) ;
+        Ok(self.conflicts(collection, loans)
+            .into_iter()
+            .filter(|conflict| conflict.decks.iter().any(|(deck, _)| same_name(deck, &name)))
+            .collect())
+    }
+
+    /// Disassembles a deck, releasing its copies, and returns it
+    pub fn disassemble(&mut self, name: &str) -> Option<Deck<'gc>>
+    {
+        let position = self.decks.iter().position(|deck| same_name(&deck.name, name))? ;
+        Some(self.decks.remove(position))
+    }
+
+    /// Built decks, in the order they were built
+    pub fn iter(&self) -> std::slice::Iter<'_, Deck<'gc>>
+    {
+        self.decks.iter()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Deck<'gc>>
+    {
+        self.decks.iter().find(|deck| same_name(&deck.name, name))
+    }
+
+    /// Number of copies of a printing reserved by the built decks
+    pub fn reserved(&self, card: &GenericCard<'_>) -> usize
+    {
+        self.decks.iter().map(|deck| deck.count(card)).sum()
+    }
+
+    /// Printings which the built decks need more copies of than the collection owns and has not lent, in the order decks need them
+    pub fn conflicts(&self, collection: &Collection<'gc>, loans: &LoanLedger<'gc>) -> Vec<Conflict<'gc>>
+    {
+        let mut conflicts: Vec<Conflict<'gc>> = vec![] ;
+        for (card, _) in self.decks.iter().flat_map(|deck| deck.cards())
+        {
+            if conflicts.iter().any(|conflict| conflict.card.same_printing(card)) { continue ; }
+
+            let owned = owned(collection, card) ;
+            let lent = loans.outstanding_count(card, Direction::Lent) ;
+            let decks: Vec<(String, usize)> = self.decks.iter()
+                .map(|deck| (deck.name.clone(), deck.count(card)))
+                .filter(|(_, count)| *count > 0)
+                .collect() ;
+            if decks.iter().map(|(_, count)| count).sum::<usize>() > owned.saturating_sub(lent)
+            {
+                conflicts.push(Conflict { card: card.clone(), owned, lent, decks }) ;
+            }
+        }
+        conflicts
+    }
+
+    /// Number of copies of a card which are neither reserved by a deck nor lent: owned − reserved − lent
+    pub fn available<'any, T>(&self, collection: &Collection<'gc>, loans: &LoanLedger<'gc>, id: T) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let card = collection.resolve(id, collection.policy())? ;
+        Ok(self.availability_of(collection, loans, card).available())
+    }
+
+    /// Availability of every card owned or reserved, in collection order
+    pub fn availability<'c>(&self, collection: &'c Collection<'gc>, loans: &LoanLedger<'gc>) -> Vec<Availability<'c, 'gc>>
+    {
+        collection.stocks()
+            .map(|(card, _)| self.availability_of(collection, loans, card))
+            .filter(|availability| availability.owned > 0 || availability.reserved > 0)
+            .collect()
+    }
+
+    fn availability_of<'c>(&self, collection: &Collection<'gc>, loans: &LoanLedger<'gc>, card: &'c GenericCard<'gc>) -> Availability<'c, 'gc>
+    {
+        Availability
+        {
+            card,
+            owned: owned(collection, card),
+            reserved: self.reserved(card),
+            lent: loans.outstanding_count(card, Direction::Lent),
+        }
+    }
+}
+
+fn owned(collection: &Collection<'_>, card: &GenericCard<'_>) -> usize
+{
+    collection.stocks()
+        .filter(|(known, _)| known.same_printing(card))
+        .map(|(_, stock)| stock.total())
+        .sum()
+}
+
+/// A printing which the built decks need more copies of than are owned and not lent
+#[derive(Debug, Clone)]
+pub struct Conflict<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub owned: usize,
+    pub lent: usize,
+    /// Decks reserving copies of the card, and how many they reserve
+    pub decks: Vec<(String, usize)>,
+}
+
+impl Conflict<'_>
+{
+    /// Number of copies the decks need
+    pub fn needed(&self) -> usize
+    {
+        self.decks.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Number of copies to get (or to take back) for every deck to be complete
+    pub fn missing(&self) -> usize
+    {
+        self.needed().saturating_sub(self.owned.saturating_sub(self.lent))
+    }
+}
+
+/// `Banish from Edoras (LTR C 0001): 4 owned, 5 needed by Boromir (3), Gondor (2)`, lent copies following owned ones
+impl std::fmt::Display for Conflict<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let decks: Vec<String> = self.decks.iter().map(|(deck, count)| format!("{} ({})", deck, count)).collect() ;
+        write!(f, "{}: {} owned, ", self.card, self.owned)? ;
+        if self.lent > 0
+        {
+            write!(f, "{} lent, ", self.lent)? ;
+        }
+        write!(f, "{} needed by {}", self.needed(), decks.join(", "))
+    }
+}
+
+/// Copies of a card, owned, reserved by decks and lent
+#[derive(Debug, Clone, Copy)]
+pub struct Availability<'c, 'gc>
+{
+    pub card: &'c GenericCard<'gc>,
+    pub owned: usize,
+    pub reserved: usize,
+    pub lent: usize,
+}
+
+impl Availability<'_, '_>
+{
+    /// owned − reserved − lent, stopping at 0
+    pub fn available(&self) -> usize
+    {
+        self.owned.saturating_sub(self.reserved).saturating_sub(self.lent)
+    }
+}
+
+#[test]
+fn decks_reservations()
+{
+    use crate::dates::Timestamp ;
+
+    let catalog = crate::collections::test_catalog() ;
+    let collection = Collection::from_counts(&catalog, [("LTR C 0001", 4), ("LTC R 0001", 2), ("LTR R 0004", 1)]).unwrap() ;
+    let mut loans = LoanLedger::default() ;
+    loans.lend(&collection, &Decks::default(), "LTR C 0001", "Sam", 1, Timestamp::from_date(2026, 1, 1).unwrap()).unwrap() ;
+
+    let mut boromir = Deck::new("Boromir") ;
+    boromir.add(&collection, "LTR C 0001", 2).unwrap() ;
+    boromir.add(&collection, "LTR R 0004", 1).unwrap() ;
+    assert_eq!(boromir.add(&collection, "LTR C 0001", 1), Ok(3)) ;
+    assert!(boromir.add(&collection, "Banish from Edoras", 1).is_err()) ;
+    assert_eq!(boromir.total(), 4) ;
+
+    let (gondor, report) = Deck::from_decklist(&collection, "Gondor", "Deck\n2 Banish from Edoras (LTC) 1\n1 LTR R 0004\n\nSideboard\n1 Banish from Edoras (LTR) 1\n1 Sauron\n") ;
+    assert_eq!(report.unmatched.len(), 1) ;
+    assert_eq!(gondor.cards().map(|(card, count)| (card.printing().serial(), count)).collect::<Vec<_>>(), vec![
+        (Some("LTR C 0001"), 1),
+        (Some("LTR R 0004"), 1),
+        (Some("LTC R 0001"), 2),
+    ]) ;
+
+    let mut decks = Decks::default() ;
+    assert!(decks.build(&collection, &loans, boromir).unwrap().is_empty()) ;
+    let conflicts = decks.build(&collection, &loans, gondor).unwrap() ;
+    // The copy lent to Sam is missing for Gondor
+    assert_eq!(conflicts.iter().map(|conflict| (conflict.card.printing().serial(), conflict.owned, conflict.missing())).collect::<Vec<_>>(), vec![
+        (Some("LTR C 0001"), 4, 1),
+        (Some("LTR R 0004"), 1, 1),
+    ]) ;
+    assert_eq!(conflicts[0].to_string(), "Banish from Edoras (LTR C 0001): 4 owned, 1 lent, 4 needed by Boromir (3), Gondor (1)") ;
+    assert_eq!(decks.conflicts(&collection, &loans).len(), 2) ;
+    assert!(decks.build(&collection, &loans, Deck::new(" gondor ")).is_err()) ;
+    assert_eq!(loans.lend(&collection, &decks, "LTC R 0001", "Sam", 1, Timestamp::from_date(2026, 1, 2).unwrap()).unwrap_err(), CollectionError::InsufficientQuantity { owned: 0, requested: 1 }) ;
+
+    // 4 owned, 4 reserved and 1 lent
+    assert_eq!(decks.available(&collection, &loans, "LTR C 0001"), Ok(0)) ;
+    assert_eq!(decks.available(&collection, &loans, "LTC R 0001"), Ok(0)) ;
+    assert_eq!(decks.availability(&collection, &loans).iter().map(|a| (a.card.printing().serial(), a.owned, a.reserved, a.lent)).collect::<Vec<_>>(), vec![
+        (Some("LTR C 0001"), 4, 4, 1),
+        (Some("LTR R 0004"), 1, 2, 0),
+        (Some("LTC R 0001"), 2, 2, 0),
+    ]) ;
+
+    assert_eq!(decks.disassemble("BOROMIR").map(|deck| deck.total()), Some(4)) ;
+    assert!(decks.disassemble("Boromir").is_none()) ;
+    assert!(decks.conflicts(&collection, &loans).is_empty()) ;
+    assert_eq!(decks.available(&collection, &loans, "LTR C 0001"), Ok(2)) ;
+    assert_eq!(decks.iter().map(|deck| deck.name.as_str()).collect::<Vec<_>>(), vec!["Gondor"]) ;
+    assert_eq!(decks.get("gondor").map(|deck| deck.count(catalog.resolve("LTR R 0004", catalog.policy()).unwrap())), Some(1)) ;
+}
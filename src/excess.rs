@@ -0,0 +1,338 @@
+use crate::cards::GenericCard;
+use crate::collections::Collection;
+use crate::decks::Decks;
+use crate::exchange::{Conversion, Target};
+use crate::loans::{Direction, LoanLedger};
+use crate::money::{Amount, Money};
+use crate::names::same_name;
+use crate::stock::Stock;
+use crate::prices::PriceSource;
+use crate::Rarity;
+
+/// How many copies of each card to keep, whatever their printing
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeepPolicy
+{
+    pub default: usize,
+    /// Card names, and how many copies of them to keep
+    overrides: Vec<(String, usize)>,
+}
+
+impl KeepPolicy
+{
+    /// Keeps a playset of every card, for constructed formats
+    pub fn constructed() -> KeepPolicy
+    {
+        KeepPolicy::keeping(crate::completion::PLAYSET)
+    }
+
+    /// Keeps one copy of every card, for singleton formats
+    pub fn singleton() -> KeepPolicy
+    {
+        KeepPolicy::keeping(1)
+    }
+
+    pub fn keeping(default: usize) -> KeepPolicy
+    {
+        KeepPolicy { default, overrides: vec![] }
+    }
+
+    /// Keeps another number of copies of a card, by name
+    pub fn with_override(mut self, name: &str, keep: usize) -> KeepPolicy
+    {
+        self.overrides.retain(|(known, _)| !same_name(known, name)) ;
+        self.overrides.push((name.trim().to_string(), keep)) ;
+        self
+    }
+
+    /// Number of copies of a card to keep
+    pub fn keep(&self, card: &GenericCard<'_>) -> usize
+    {
+        self.overrides.iter()
+            .find(|(name, _)| same_name(name, card.name()))
+            .map_or(self.default, |(_, keep)| *keep)
+    }
+}
+
+impl Default for KeepPolicy
+{
+    fn default() -> Self
+    {
+        KeepPolicy::constructed()
+    }
+}
+
+/// Copies of a printing beyond the copies of the card to keep
+#[derive(Debug, Clone)]
+pub struct Excess<'gc>
+{
+    pub card: GenericCard<'gc>,
+    /// The excess copies, by variant and location
+    pub stock: Stock,
+    /// Value of the excess copies in the currency of the report, `None` if some of them have no price
+    pub value: Option<Amount>,
+}
+
+impl Excess<'_>
+{
+    pub fn count(&self) -> usize
+    {
+        self.stock.total()
+    }
+}
+
+/// Lists the copies owned beyond what the policy keeps, in collection order<br/>
+/// Copies of a card are counted across its printings, those found first in the collection being kept first;
+/// within a printing, unsorted copies of the default variant are the first excess ones (see [`Stock::take`]).
+/// Copies reserved by a deck or lent are never excess: they are kept, even beyond what the policy keeps.
+/// Copies quoted in a currency without rate to the target one are unpriced
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::decks::Decks;
+/// use mtg_multitool::exchange::ExchangeRates;
+/// use mtg_multitool::excess::{find, KeepPolicy};
+/// use mtg_multitool::loans::LoanLedger;
+/// use mtg_multitool::money::Currency;
+/// use mtg_multitool::prices::no_prices;
+/// use mtg_multitool::ltr;
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 6), ("LTR R 0004", 2)]).unwrap() ;
+/// let (decks, loans, rates) = (Decks::default(), LoanLedger::default(), ExchangeRates::default()) ;
+/// let euros = rates.to(Currency::try_from("EUR").unwrap(), Timestamp::now()) ;
+///
+/// let constructed = find(&collection, &decks, &loans, &KeepPolicy::constructed(), &no_prices, euros) ;
+/// assert_eq!(constructed.excess.iter().map(|excess| (*excess.card.name(), excess.count())).collect::<Vec<_>>(), vec![("Banish from Edoras", 2)]) ;
+///
+/// let singleton = find(&collection, &decks, &loans, &KeepPolicy::singleton().with_override("Banish from Edoras", 4), &no_prices, euros) ;
+/// assert_eq!(singleton.excess.iter().map(|excess| (*excess.card.name(), excess.count())).collect::<Vec<_>>(), vec![
+///     ("Banish from Edoras", 2),
+///     ("Boromir, Warden of the Tower", 1),
+/// ]) ;
+/// ```
+pub fn find<'gc, P: PriceSource>(collection: &Collection<'gc>, decks: &Decks<'gc>, loans: &LoanLedger<'gc>, policy: &KeepPolicy, prices: &P, target: Target<'_>) -> ExcessReport<'gc>
+{
+    let mut conversion = target.conversion() ;
+    // Names of the cards seen so far, and how many of their copies are kept
+    let mut kept: Vec<(&str, usize)> = vec![] ;
+    let mut excess = vec![] ;
+
+    for (card, stock) in collection.stocks()
+    {
+        let keep = policy.keep(card) ;
+        let position = match kept.iter().position(|(name, _)| same_name(name, card.name()))
+        {
+            Some(position) => position,
+            None => {
+                kept.push((card.name(), 0)) ;
+                kept.len() - 1
+            },
+        } ;
+
+        let in_use = decks.reserved(card) + loans.outstanding_count(card, Direction::Lent) ;
+        let kept_here = stock.total().min(in_use.max(keep.saturating_sub(kept[position].1))) ;
+        kept[position].1 += kept_here ;
+        if stock.total() == kept_here { continue ; }
+
+        let mut excess_stock = Stock::default() ;
+        let mut remaining = stock.clone() ;
+        remaining.take(stock.total() - kept_here) ;
+        for lot in stock.lots()
+        {
+            let count = lot.count - remaining.count_at(&lot.variant, lot.location.as_deref()).min(lot.count) ;
+            excess_stock.add_at(lot.variant, lot.location.as_deref(), count) ;
+        }
+
+        let values = excess_stock.iter()
+            .map(|(variant, count)| prices.price(card, variant).filter(|price| target.learn(&mut conversion, price.currency)).map(|price| price * count))
+            .collect::<Option<Vec<Money>>>() ;
+        let value = values.map(|values| conversion.total(values)) ;
+        excess.push(Excess { card: card.clone(), stock: excess_stock, value }) ;
+    }
+
+    ExcessReport { excess, conversion }
+}
+
+/// Copies owned beyond what is kept, see [`find`]
+#[derive(Debug, Clone)]
+pub struct ExcessReport<'gc>
+{
+    pub excess: Vec<Excess<'gc>>,
+    /// Currency of the values, and exchange rates used to convert to it
+    pub conversion: Conversion,
+}
+
+impl<'gc> ExcessReport<'gc>
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.excess.is_empty()
+    }
+
+    /// Number of excess copies
+    pub fn total(&self) -> usize
+    {
+        self.excess.iter().map(Excess::count).sum()
+    }
+
+    /// Value of the priced excess copies
+    pub fn value(&self) -> Amount
+    {
+        self.excess.iter().filter_map(|excess| excess.value).sum()
+    }
+
+    /// Excess grouped by set, sets in collection order (`None` for cards without serial)
+    pub fn by_set(&self) -> Vec<(Option<String>, Vec<&Excess<'gc>>)>
+    {
+        let mut sets: Vec<(Option<String>, Vec<&Excess<'gc>>)> = vec![] ;
+        for excess in &self.excess
+        {
+            let set = excess.card.printing().set() ;
+            match sets.iter_mut().find(|(known, _)| *known == set)
+            {
+                Some((_, group)) => group.push(excess),
+                None => sets.push((set, vec![excess])),
+            }
+        }
+        sets
+    }
+
+    /// Excess grouped by rarity, from commons to mythics
+    pub fn by_rarity(&self) -> Vec<(Rarity, Vec<&Excess<'gc>>)>
+    {
+        let mut rarities: Vec<(Rarity, Vec<&Excess<'gc>>)> = vec![] ;
+        for excess in &self.excess
+        {
+            let rarity = *excess.card.rarity() ;
+            match rarities.iter_mut().find(|(known, _)| *known == rarity)
+            {
+                Some((_, group)) => group.push(excess),
+                None => rarities.push((rarity, vec![excess])),
+            }
+        }
+        rarities.sort_by_key(|(rarity, _)| *rarity as u8) ;
+        rarities
+    }
+
+    /// Excess from the most valuable to the least, unpriced copies last
+    pub fn by_value(&self) -> Vec<&Excess<'gc>>
+    {
+        let mut excess: Vec<&Excess<'gc>> = self.excess.iter().collect() ;
+        excess.sort_by_key(|excess| std::cmp::Reverse(excess.value)) ;
+        excess
+    }
+
+    /// The excess copies as a collection of the same cards, to be exported as a trade binder
+    /// (see [`crate::exports::export`])
+    pub fn to_collection(&self, collection: &Collection<'gc>) -> Collection<'gc>
+    {
+        let mut binder = Collection::from_counts(collection, std::iter::empty::<(&str, usize)>())
+            .expect("An empty list of counts always resolves") ;
+        binder.extend(self.excess.iter().map(|excess| (excess.card.clone(), excess.stock.clone()))) ;
+        binder
+    }
+}
+
+/// A trade binder list: one line per excess printing, grouped by set, followed by the total and the exchange rates used
+/// ```text
+/// LTR
+///   2 Banish from Edoras (LTR C 0001): 0.50 EUR
+///   1 Boromir, Warden of the Tower (LTR R 0004), unpriced
+/// 3 copies, 0.50 EUR
+/// Rate: 1 USD = 0.75 EUR (2026-10-01)
+/// ```
+impl std::fmt::Display for ExcessReport<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        for (set, group) in self.by_set()
+        {
+            writeln!(f, "{}", set.as_deref().unwrap_or("(no set)"))? ;
+            for excess in group
+            {
+                write!(f, "  {} {}", excess.count(), excess.card)? ;
+                match excess.value
+                {
+                    Some(value) => writeln!(f, ": {}", Money::new(value, self.conversion.currency))?,
+                    None => writeln!(f, ", unpriced")?,
+                }
+            }
+        }
+        writeln!(f, "{} copies, {}", self.total(), Money::new(self.value(), self.conversion.currency))? ;
+        write!(f, "{}", self.conversion)
+    }
+}
+
+#[test]
+fn excess_report()
+{
+    use crate::exchange::ExchangeRates ;
+    use crate::money::Currency ;
+    use crate::stock::Variant ;
+
+    let catalog = crate::collections::test_catalog() ;
+    let (eur, usd) = (Currency::try_from("EUR").unwrap(), Currency::try_from("USD").unwrap()) ;
+    let rates = ExchangeRates::from_csv("date,from,to,rate\n2026-10-01,USD,EUR,0.75\n").unwrap() ;
+    let euros = rates.to(eur, crate::dates::Timestamp::now()) ;
+    let foil = Variant { finish: crate::Finish::Foil, ..Variant::default() } ;
+    let mut collection = Collection::from_counts(&catalog, [("LTR C 0001", 3), ("LTC R 0001", 2), ("LTR R 0004", 6), ("Homemade card", 2)]).unwrap() ;
+    collection.add_at("LTR C 0001", foil, "Binder 1", 2).unwrap() ;
+
+    let prices = |card: &GenericCard<'_>, variant: &Variant| match (*card.name(), variant.finish)
+    {
+        ("Banish from Edoras", crate::Finish::Foil) => Some(Money::new(Amount::from_cents(100), eur)),
+        ("Banish from Edoras", _) => Some(Money::new(Amount::from_cents(25), eur)),
+        ("Boromir, Warden of the Tower", _) => Some(Money::new(Amount::from_cents(200), usd)),
+        _ => None,
+    } ;
+
+    // 7 Banish across printings, the 4 first LTR ones being kept. Boromir is 4.00 USD, that is 3.00 EUR
+    let (decks, loans) = (Decks::default(), LoanLedger::default()) ;
+    let report = find(&collection, &decks, &loans, &KeepPolicy::constructed().with_override("homemade card", 1), &prices, euros) ;
+    assert_eq!(report.excess.iter().map(|excess| (excess.card.printing().serial(), excess.count(), excess.value)).collect::<Vec<_>>(), vec![
+        (Some("LTR C 0001"), 1, Some(Amount::from_cents(25))),
+        (Some("LTR R 0004"), 2, Some(Amount::from_cents(300))),
+        (Some("LTC R 0001"), 2, Some(Amount::from_cents(50))),
+        (None, 1, None),
+    ]) ;
+    assert_eq!(report.excess[0].stock.lots().map(|lot| (lot.variant, lot.location.as_deref(), lot.count)).collect::<Vec<_>>(), vec![(Variant::default(), None, 1)]) ;
+    assert_eq!((report.total(), report.value()), (6, Amount::from_cents(375))) ;
+
+    assert_eq!(report.by_set().iter().map(|(set, group)| (set.clone(), group.len())).collect::<Vec<_>>(), vec![
+        (Some("LTR".to_string()), 2),
+        (Some("LTC".to_string()), 1),
+        (None, 1),
+    ]) ;
+    assert_eq!(report.by_rarity().iter().map(|(rarity, group)| (*rarity, group.len())).collect::<Vec<_>>(), vec![(Rarity::Common, 2), (Rarity::Rare, 2)]) ;
+    assert_eq!(report.by_value().iter().map(|excess| excess.card.printing().serial()).collect::<Vec<_>>(), vec![
+        Some("LTR R 0004"), Some("LTC R 0001"), Some("LTR C 0001"), None,
+    ]) ;
+
+    let binder = report.to_collection(&collection) ;
+    assert_eq!(binder.total(), 6) ;
+    assert_eq!(binder.count_variant("LTR C 0001", &foil), Some(0)) ;
+    assert_eq!(
+        report.to_string(),
+        "LTR\n  1 Banish from Edoras (LTR C 0001): 0.25 EUR\n  2 Boromir, Warden of the Tower (LTR R 0004): 3.00 EUR\n\
+         LTC\n  2 Banish from Edoras (LTC R 0001): 0.50 EUR\n\
+         (no set)\n  1 Homemade card, unpriced\n\
+         6 copies, 3.75 EUR\n\
+         Rate: 1 USD = 0.75 EUR (2026-10-01)\n"
+    ) ;
+
+    assert!(find(&collection, &decks, &loans, &KeepPolicy::keeping(10), &prices, euros).is_empty()) ;
+
+    // Reserved and lent copies are kept first
+    let mut deck = crate::decks::Deck::new("Gondor") ;
+    deck.add(&collection, "LTC R 0001", 2).unwrap() ;
+    deck.add(&collection, "LTR R 0004", 4).unwrap() ;
+    let mut decks = Decks::default() ;
+    decks.build(&collection, &loans, deck).unwrap() ;
+    let mut loans = LoanLedger::default() ;
+    loans.lend(&collection, &decks, "LTR R 0004", "Sam", 1, crate::dates::Timestamp::from_date(2026, 1, 1).unwrap()).unwrap() ;
+    let report = find(&collection, &decks, &loans, &KeepPolicy::constructed(), &prices, euros) ;
+    assert_eq!(report.excess.iter().map(|excess| (excess.card.printing().serial(), excess.count())).collect::<Vec<_>>(), vec![
+        (Some("LTR C 0001"), 1),
+        (Some("LTR R 0004"), 1),
+    ]) ;
+}
@@ -0,0 +1,313 @@
+use crate::dates::Timestamp;
+use crate::imports::ImportError;
+use crate::money::{divide, parse_decimal, Amount, Currency, Money};
+
+/// Rates are kept in millionths, to stay exact
+const MICROS: u64 = 1_000_000 ;
+
+/// Column titles of exchange-rate files, the first one being the expected one
+const DATE_COLUMN: [&str ; 1] = ["Date"] ;
+const FROM_COLUMN: [&str ; 2] = ["From", "Base"] ;
+const TO_COLUMN: [&str ; 2] = ["To", "Quote"] ;
+const RATE_COLUMN: [&str ; 1] = ["Rate"] ;
+
+/// How much of a currency one unit of another is worth, as of a date<br/>
+/// Written as `1 EUR = 1.0842 USD (2026-10-01)`, rates having at most 6 decimals
+/// ```
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::exchange::ExchangeRate;
+/// use mtg_multitool::money::{Amount, Currency};
+///
+/// let eur = Currency::try_from("EUR").unwrap() ;
+/// let usd = Currency::try_from("USD").unwrap() ;
+/// let rate = ExchangeRate::new(eur, usd, "1.0842", Timestamp::from_date(2026, 10, 1).unwrap()).unwrap() ;
+/// assert_eq!(rate.convert(Amount::from_cents(1000)).to_string(), "10.842") ;
+/// assert_eq!(rate.to_string(), "1 EUR = 1.0842 USD (2026-10-01)") ;
+/// assert_eq!(rate.inverse().to_string(), "1 USD = 0.922339 EUR (2026-10-01)") ;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExchangeRate
+{
+    pub from: Currency,
+    pub to: Currency,
+    pub date: Timestamp,
+    micros: u64,
+}
+
+impl ExchangeRate
+{
+    /// Reads the rate from `1.0842`, `1,0842` or `1`, which must be above 0
+    pub fn new(from: Currency, to: Currency, rate: &str, date: Timestamp) -> Result<ExchangeRate, &'static str>
+    {
+        let micros = parse_decimal(rate, 6)? ;
+        if micros <= 0 { return Err("Rates must be above 0") ; }
+        let micros = u64::try_from(micros).map_err(|_| "Number too large")? ;
+        Ok(ExchangeRate { from, to, date, micros })
+    }
+
+    /// The rate the other way round, rounded to 6 decimals
+    pub fn inverse(&self) -> ExchangeRate
+    {
+        let micros = (u128::from(MICROS) * u128::from(MICROS) + u128::from(self.micros) / 2) / u128::from(self.micros) ;
+        ExchangeRate { from: self.to, to: self.from, date: self.date, micros: u64::try_from(micros).unwrap_or(u64::MAX).max(1) }
+    }
+
+    /// An amount of the `from` currency in the `to` currency, rounded to the millionth
+    pub fn convert(&self, amount: Amount) -> Amount
+    {
+        Amount::from_micros(divide(amount.micros().saturating_mul(i128::from(self.micros)), i128::from(MICROS)))
+    }
+}
+
+impl std::fmt::Display for ExchangeRate
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let decimals = format!("{:06}", self.micros % MICROS) ;
+        let decimals = decimals.trim_end_matches('0') ;
+        let separator = if decimals.is_empty() { "" } else { "." } ;
+        write!(f, "1 {} = {}{}{} {} ({})", self.from, self.micros / MICROS, separator, decimals, self.to, self.date.date_string())
+    }
+}
+
+/// Dated exchange rates, read from a local file
+/// ```
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::exchange::ExchangeRates;
+/// use mtg_multitool::money::{Amount, Currency, Money};
+///
+/// let rates = ExchangeRates::from_csv("date,from,to,rate\n2026-09-01,EUR,USD,1.10\n2026-10-01,EUR,USD,1.0842\n").unwrap() ;
+/// let (eur, usd) = (Currency::try_from("EUR").unwrap(), Currency::try_from("USD").unwrap()) ;
+///
+/// let september = Timestamp::from_date(2026, 9, 15).unwrap() ;
+/// assert_eq!(rates.convert(Money::new(Amount::from_cents(1000), eur), usd, september), Some(Money::new(Amount::from_cents(1100), usd))) ;
+/// assert_eq!(rates.rate(usd, eur, Timestamp::now()).unwrap().to_string(), "1 USD = 0.922339 EUR (2026-10-01)") ;
+/// assert_eq!(rates.rate(eur, usd, Timestamp::from_date(2026, 8, 31).unwrap()), None) ;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeRates
+{
+    rates: Vec<ExchangeRate>,
+}
+
+impl ExchangeRates
+{
+    /// Reads a csv file with `date`, `from`, `to` and `rate` columns, one unit of `from` being worth `rate` of `to`
+    pub fn from_csv(text: &str) -> Result<ExchangeRates, ImportError>
+    {
+        let mut records = crate::csv::records(text, ',')
+            .map_err(|(line, reason)| ImportError::Malformed { line, reason })?
+            .into_iter() ;
+        let (_, header) = records.next().ok_or(ImportError::MissingColumn(DATE_COLUMN[0]))? ;
+
+        let find = |aliases: &[&'static str]| header.iter().position(|title| aliases.iter().any(|alias| crate::names::same_name(title, alias))) ;
+        let date = find(&DATE_COLUMN).ok_or(ImportError::MissingColumn(DATE_COLUMN[0]))? ;
+        let from = find(&FROM_COLUMN).ok_or(ImportError::MissingColumn(FROM_COLUMN[0]))? ;
+        let to = find(&TO_COLUMN).ok_or(ImportError::MissingColumn(TO_COLUMN[0]))? ;
+        let rate = find(&RATE_COLUMN).ok_or(ImportError::MissingColumn(RATE_COLUMN[0]))? ;
+
+        let mut rates = ExchangeRates::default() ;
+        for (line, fields) in records
+        {
+            let field = |index: usize| fields.get(index).map(String::as_str).unwrap_or_default() ;
+            let malformed = |reason: &'static str| ImportError::Malformed { line, reason } ;
+
+            rates.push(ExchangeRate::new(
+                Currency::try_from(field(from)).map_err(malformed)?,
+                Currency::try_from(field(to)).map_err(malformed)?,
+                field(rate),
+                Timestamp::try_from(field(date)).map_err(malformed)?,
+            ).map_err(malformed)?) ;
+        }
+        Ok(rates)
+    }
+
+    pub fn push(&mut self, rate: ExchangeRate)
+    {
+        self.rates.push(rate) ;
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.rates.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.rates.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ExchangeRate>
+    {
+        self.rates.iter()
+    }
+
+    /// Latest rate from a currency to another as of a date, that is the latest one not after it<br/>
+    /// Rates the other way round are inverted, rates as given being preferred on the same date
+    pub fn rate(&self, from: Currency, to: Currency, date: Timestamp) -> Option<ExchangeRate>
+    {
+        self.rates.iter()
+            .filter(|rate| rate.date <= date)
+            .filter_map(|rate| match (rate.from, rate.to)
+            {
+                pair if pair == (from, to) => Some((rate.date, true, *rate)),
+                pair if pair == (to, from) => Some((rate.date, false, rate.inverse())),
+                _ => None,
+            })
+            .max_by_key(|(date, direct, _)| (*date, *direct))
+            .map(|(_, _, rate)| rate)
+    }
+
+    /// Money in another currency, at the rate as of a date; `None` if no such rate is known
+    pub fn convert(&self, money: Money, to: Currency, date: Timestamp) -> Option<Money>
+    {
+        if money.currency == to { return Some(money) ; }
+        self.rate(money.currency, to, date).map(|rate| Money::new(rate.convert(money.amount), to))
+    }
+
+    /// The currency to convert money to, with the rates as of a date
+    pub fn to(&self, currency: Currency, date: Timestamp) -> Target<'_>
+    {
+        Target { currency, rates: self, date }
+    }
+}
+
+/// A currency to convert prices to, with the exchange rates as of a date, see [`ExchangeRates::to`]
+#[derive(Debug, Clone, Copy)]
+pub struct Target<'r>
+{
+    pub currency: Currency,
+    pub rates: &'r ExchangeRates,
+    pub date: Timestamp,
+}
+
+impl Target<'_>
+{
+    /// A conversion to the target currency, which has not used any rate yet
+    pub fn conversion(&self) -> Conversion
+    {
+        Conversion { currency: self.currency, rates: vec![] }
+    }
+
+    /// Adds the rate from a currency to a conversion if it needs it; returns whether money in that currency can be converted
+    pub fn learn(&self, conversion: &mut Conversion, from: Currency) -> bool
+    {
+        if conversion.accepts(from) { return true ; }
+        match self.rates.rate(from, self.currency, self.date)
+        {
+            Some(rate) => {
+                conversion.rates.push(rate) ;
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Conversion of money to a currency, with the exchange rates it uses (see [`Target`])<br/>
+/// Money is summed in each currency first, then each sum is converted, for totals not to add up rounding errors
+/// ```
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::exchange::ExchangeRates;
+/// use mtg_multitool::money::{Amount, Currency, Money};
+///
+/// let rates = ExchangeRates::from_csv("date,from,to,rate\n2026-10-01,EUR,USD,1.0842\n").unwrap() ;
+/// let (eur, usd) = (Currency::try_from("EUR").unwrap(), Currency::try_from("USD").unwrap()) ;
+/// let target = rates.to(usd, Timestamp::now()) ;
+///
+/// let mut conversion = target.conversion() ;
+/// assert!(target.learn(&mut conversion, eur)) ;
+/// let prices = [Money::new(Amount::from_cents(25), eur), Money::new(Amount::from_cents(25), eur), Money::new(Amount::from_cents(100), usd)] ;
+/// assert_eq!(conversion.total(prices).to_string(), "1.5421") ;
+/// assert_eq!(conversion.to_string(), "Rate: 1 EUR = 1.0842 USD (2026-10-01)\n") ;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion
+{
+    pub currency: Currency,
+    /// Rates to the currency, in order of first use
+    pub rates: Vec<ExchangeRate>,
+}
+
+impl Conversion
+{
+    /// Whether money in a currency can be converted
+    pub fn accepts(&self, currency: Currency) -> bool
+    {
+        currency == self.currency || self.rates.iter().any(|rate| rate.from == currency)
+    }
+
+    /// Money in the currency of the conversion, `None` if it has no rate for it
+    pub fn convert(&self, money: Money) -> Option<Amount>
+    {
+        if money.currency == self.currency { return Some(money.amount) ; }
+        self.rates.iter().find(|rate| rate.from == money.currency).map(|rate| rate.convert(money.amount))
+    }
+
+    /// Sum of money in several currencies, money which cannot be converted being left out
+    pub fn total(&self, values: impl IntoIterator<Item = Money>) -> Amount
+    {
+        let mut sums: Vec<Money> = vec![] ;
+        for value in values
+        {
+            match sums.iter_mut().find(|sum| sum.currency == value.currency)
+            {
+                Some(sum) => sum.amount = sum.amount + value.amount,
+                None => sums.push(value),
+            }
+        }
+        sums.into_iter().filter_map(|sum| self.convert(sum)).sum()
+    }
+}
+
+/// One `Rate: 1 EUR = 1.0842 USD (2026-10-01)` line per rate used
+impl std::fmt::Display for Conversion
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        for rate in &self.rates
+        {
+            writeln!(f, "Rate: {}", rate)? ;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn exchange_rates()
+{
+    let currency = |code| Currency::try_from(code).unwrap() ;
+    let (eur, usd, gbp) = (currency("EUR"), currency("USD"), currency("GBP")) ;
+    let day = |month, day| Timestamp::from_date(2026, month, day).unwrap() ;
+
+    assert_eq!(ExchangeRate::new(eur, usd, "0", day(1, 1)), Err("Rates must be above 0")) ;
+    assert_eq!(ExchangeRate::new(eur, usd, "-1", day(1, 1)), Err("Rates must be above 0")) ;
+    assert_eq!(ExchangeRate::new(eur, usd, "1.1234567", day(1, 1)), Err("Too many decimals")) ;
+    assert_eq!(ExchangeRate::new(eur, usd, "1", day(1, 1)).unwrap().to_string(), "1 EUR = 1 USD (2026-01-01)") ;
+    let rate = ExchangeRate::new(usd, eur, ",5", day(1, 1)).unwrap() ;
+    assert_eq!((rate.convert(Amount::from_cents(-3)).to_string(), rate.inverse().convert(Amount::from_cents(250))), ("-0.015".to_string(), Amount::from_cents(500))) ;
+
+    let rates = ExchangeRates::from_csv("Date,Base,Quote,Rate\n\
+                                         2026-09-01,EUR,USD,1.10\n\
+                                         2026-10-01,usd,eur,0.90\n\
+                                         2026-10-01,EUR,USD,1.12\n\
+                                         2026-10-01,EUR,GBP,0.85\n").unwrap() ;
+    assert_eq!(rates.len(), 4) ;
+    assert_eq!(rates.rate(eur, usd, day(10, 1)).map(|rate| rate.to_string()), Some("1 EUR = 1.12 USD (2026-10-01)".to_string())) ;
+    assert_eq!(rates.rate(usd, eur, day(10, 1)).map(|rate| rate.to_string()), Some("1 USD = 0.9 EUR (2026-10-01)".to_string())) ;
+    assert_eq!(rates.rate(usd, eur, day(9, 30)).map(|rate| rate.to_string()), Some("1 USD = 0.909091 EUR (2026-09-01)".to_string())) ;
+    assert_eq!(rates.rate(usd, gbp, day(10, 1)), None) ;
+    assert_eq!(rates.convert(Money::new(Amount::from_cents(1000), gbp), gbp, day(1, 1)), Some(Money::new(Amount::from_cents(1000), gbp))) ;
+    assert_eq!(rates.convert(Money::new(Amount::from_cents(1000), eur), gbp, day(10, 1)), Some(Money::new(Amount::from_cents(850), gbp))) ;
+
+    // Sums are converted once per currency
+    let target = rates.to(usd, day(10, 1)) ;
+    let mut conversion = target.conversion() ;
+    assert!(target.learn(&mut conversion, usd) && target.learn(&mut conversion, eur) && !target.learn(&mut conversion, gbp)) ;
+    assert_eq!(conversion.rates.len(), 1) ;
+    let third = Money::new(Amount::try_from("0.333333").unwrap(), eur) ;
+    assert_eq!(conversion.total([third, third, third, Money::new(Amount::from_cents(100), gbp)]).to_string(), "1.119999") ;
+
+    assert_eq!(ExchangeRates::from_csv("date,from,to,rate\n2026-10-01,EUR,dollars,1.1\n").unwrap_err(), ImportError::Malformed { line: 2, reason: "Expected a 3-letter currency code" }) ;
+    assert_eq!(ExchangeRates::from_csv("date,from,to\n").unwrap_err(), ImportError::MissingColumn("Rate")) ;
+}
@@ -0,0 +1,151 @@
+use std::io::Write;
+
+use crate::cards::GenericCard;
+use crate::collections::Collection;
+use crate::csv::field;
+use crate::stock::Variant;
+use crate::Finish;
+
+/// Formats a collection can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format
+{
+    /// Moxfield collection csv, see [`crate::imports::Format::Moxfield`]
+    Moxfield,
+    /// Deckbox inventory csv, see [`crate::imports::Format::Deckbox`]
+    Deckbox,
+    /// One `4 Banish from Edoras (LTR) 1` card per line, as read by [`crate::imports::Format::Arena`]<br/>
+    /// Foils are followed by `*F*` and etched foils by `*E*`; conditions and languages are left out
+    Text,
+    /// Spreadsheet-friendly csv, with one column per sortable property:
+    /// `Count,Name,Set,Number,Rarity,Type,Mana Value,Mana Cost,Finish,Condition,Language`
+    Spreadsheet,
+}
+
+/// Writes the owned cards of a collection (cards with a count of 0 are left out), in collector-number order,
+/// one row per [`Variant`]
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::exports::{export, Format};
+/// use mtg_multitool::ltr;
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4), ("LTR R 0004", 1)]).unwrap() ;
+/// let mut text = vec![] ;
+/// export(&collection, Format::Text, &mut text).unwrap() ;
+///
+/// assert_eq!(String::from_utf8(text).unwrap(), "4 Banish from Edoras (LTR) 1\n1 Boromir, Warden of the Tower (LTR) 4\n") ;
+/// ```
+pub fn export<W: Write>(collection: &Collection<'_>, format: Format, mut writer: W) -> std::io::Result<()>
+{
+    match format
+    {
+        Format::Moxfield => writeln!(writer, "Count,Tradelist Count,Name,Edition,Condition,Language,Foil,Tags,Last Modified,Collector Number,Alter,Proxy,Purchase Price")?,
+        Format::Deckbox => writeln!(writer, "Count,Tradelist Count,Name,Edition,Edition Code,Card Number,Condition,Language,Foil,Signed,Artist Proof,Altered Art,Misprint,Promo,Textless,My Price")?,
+        Format::Text => (),
+        Format::Spreadsheet => writeln!(writer, "Count,Name,Set,Number,Rarity,Type,Mana Value,Mana Cost,Finish,Condition,Language")?,
+    }
+
+    for (card, stock) in collection.stocks()
+    {
+        let name = field(card.name(), ',') ;
+        let set = card.printing().set().unwrap_or_default() ;
+        let number = card.printing().number().map(|number| number.to_string()).unwrap_or_default() ;
+
+        for (variant, count) in stock.iter()
+        {
+            let foil = match variant.finish
+            {
+                Finish::Nonfoil => "",
+                finish => finish.code(),
+            } ;
+            let (condition, language) = (variant.condition.name(), variant.language.name()) ;
+
+            match format
+            {
+                Format::Moxfield => writeln!(writer, "{},0,{},{},{},{},{},,,{},False,False,", count, name, set.to_ascii_lowercase(), condition, language, foil, number)?,
+                Format::Deckbox => writeln!(writer, "{},0,{},{},{},{},{},{},{},,,,,,,", count, name, set, set, number, condition, language, foil)?,
+                Format::Text => writeln!(writer, "{}", text_line(card, count, variant))?,
+                Format::Spreadsheet => writeln!(
+                    writer, "{},{},{},{},{},{},{},{},{},{},{}",
+                    count, name, set, number, card.rarity().name(), field(&card.kind().to_string(), ','), card.cost().value(), card.cost(),
+                    variant.finish.code(), variant.condition.code(), variant.language.code()
+                )?,
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+/// `4 Banish from Edoras (LTR) 1`, or `4 Banish from Edoras` for cards without serial, followed by the finish marker
+fn text_line(card: &GenericCard<'_>, count: usize, variant: &Variant) -> String
+{
+    let line = match (card.printing().set(), card.printing().number())
+    {
+        (Some(set), Some(number)) => format!("{} {} ({}) {}", count, card.name(), set, number),
+        _ => format!("{} {}", count, card.name()),
+    } ;
+
+    match variant.finish
+    {
+        Finish::Nonfoil => line,
+        Finish::Foil => line + " *F*",
+        Finish::Etched => line + " *E*",
+    }
+}
+
+#[cfg(test)]
+fn exported(collection: &Collection<'_>, format: Format) -> String
+{
+    let mut output = vec![] ;
+    export(collection, format, &mut output).unwrap() ;
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn export_round_trips()
+{
+    use crate::imports::{import, Format as Import} ;
+
+    let catalog = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        "LTR R 0004;Boromir, Warden of the Tower;2 w;Legendary Creature - Human Soldier;R;",
+        "LTR M 0005;\"The \"\"Quoted\"\" One\";1 w;Enchantment;M;",
+        ";Homemade card;1;Sorcery;C;",
+    ]).unwrap() ;
+    let mut collection = Collection::from_counts(&catalog, [("Banish from Edoras", 4), ("LTR R 0004", 2), ("LTR M 0005", 1), ("Homemade card", 3)]).unwrap() ;
+    let foil = Variant::new(Finish::Foil, crate::Condition::ModeratelyPlayed, crate::Language::Japanese) ;
+    let etched = Variant { finish: Finish::Etched, ..Variant::default() } ;
+    collection.add_variant("Banish from Edoras", foil, 1).unwrap() ;
+    collection.add_variant("Homemade card", etched, 2).unwrap() ;
+
+    for (export_format, import_format) in [(Format::Moxfield, Import::Moxfield), (Format::Deckbox, Import::Deckbox), (Format::Text, Import::Arena)]
+    {
+        let (imported, report) = import(&catalog, import_format, &exported(&collection, export_format)).unwrap() ;
+        assert!(report.is_complete(), "{:?}: {}", export_format, report) ;
+        assert!(collection.diff(&imported).is_empty(), "{:?}: {}", export_format, collection.diff(&imported)) ;
+        assert_eq!(imported.count_variant("Homemade card", &etched), Some(2)) ;
+        if export_format != Format::Text
+        {
+            assert_eq!(imported.stock("Banish from Edoras"), collection.stock("Banish from Edoras")) ;
+        }
+    }
+
+    assert_eq!(
+        exported(&collection, Format::Moxfield).lines().nth(2),
+        Some("1,0,Banish from Edoras,ltr,Moderately Played,Japanese,foil,,,1,False,False,")
+    ) ;
+    assert_eq!(exported(&collection, Format::Text).lines().last(), Some("2 Homemade card *E*")) ;
+}
+
+#[test]
+fn export_spreadsheet()
+{
+    let collection = Collection::from_counts(&crate::ltr::collection(), [("Banish from Edoras", 4), ("LTR R 0004", 1)]).unwrap() ;
+    assert_eq!(
+        exported(&collection, Format::Spreadsheet),
+        "Count,Name,Set,Number,Rarity,Type,Mana Value,Mana Cost,Finish,Condition,Language\n\
+         4,Banish from Edoras,LTR,1,Common,Sorcery,5,{4}{W},nonfoil,NM,en\n\
+         1,\"Boromir, Warden of the Tower\",LTR,4,Rare,Legendary Creature - Human Soldier,3,{2}{W},nonfoil,NM,en\n"
+    ) ;
+}
@@ -0,0 +1,305 @@
+use std::io::Write;
+
+use crate::cards::GenericCard;
+use crate::collections::Collection;
+use crate::dates::Timestamp;
+use crate::exchange::{Conversion, ExchangeRate, Target};
+use crate::money::{Amount, Currency, Money};
+use crate::prices::{value, PriceDb, PriceSource};
+use crate::stock::Variant;
+use crate::Finish;
+
+/// Bars of sparklines, from the lowest value to the highest
+const BARS: [char ; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'] ;
+
+/// Values over time, one point per snapshot date, from the oldest
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Series
+{
+    pub points: Vec<(Timestamp, Amount)>,
+    /// Currency of the values, if known
+    pub currency: Option<Currency>,
+    /// Exchange rates used to convert the values, as of the date of each point, in order of first use
+    pub rates: Vec<ExchangeRate>,
+}
+
+impl Series
+{
+    /// One bar per point, scaled between the lowest and the highest value (flat series are all low bars)
+    /// ```
+    /// use mtg_multitool::dates::Timestamp;
+    /// use mtg_multitool::history::Series;
+    /// use mtg_multitool::money::Amount;
+    ///
+    /// let day = |day| Timestamp::from_date(2026, 10, day).unwrap() ;
+    /// let series = Series { points: [(1, 100), (2, 150), (3, 450), (4, 800)].map(|(d, cents)| (day(d), Amount::from_cents(cents))).to_vec(), ..Series::default() } ;
+    /// assert_eq!(series.sparkline(), "▁▁▄█") ;
+    /// ```
+    pub fn sparkline(&self) -> String
+    {
+        let values = self.points.iter().map(|(_, amount)| amount.micros()) ;
+        let (Some(min), Some(max)) = (values.clone().min(), values.clone().max()) else { return String::new() } ;
+        let last = (BARS.len() - 1) as i128 ;
+        values
+            .map(|value| if max == min { 0 } else { (value - min) * last / (max - min) })
+            .map(|bar| BARS[usize::try_from(bar).unwrap_or(0)])
+            .collect()
+    }
+
+    /// Writes a `Date,Value` csv, one row per point, the title of the values giving their currency (`Value (EUR)`)
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()>
+    {
+        match self.currency
+        {
+            Some(currency) => writeln!(writer, "Date,Value ({})", currency)?,
+            None => writeln!(writer, "Date,Value")?,
+        }
+        for (date, amount) in &self.points
+        {
+            writeln!(writer, "{},{}", date.date_string(), amount)? ;
+        }
+        Ok(())
+    }
+
+    /// Adds a point, along with the exchange rates it was converted with
+    fn push(&mut self, date: Timestamp, value: Amount, conversion: &Conversion)
+    {
+        self.points.push((date, value)) ;
+        for rate in &conversion.rates
+        {
+            if !self.rates.contains(rate) { self.rates.push(*rate) ; }
+        }
+    }
+}
+
+/// Price of a copy of a card variant in a currency at each snapshot date, from its first quote on<br/>
+/// Prices are converted with the exchange rates as of each date, dates without rate being left out
+pub fn card_history(prices: &PriceDb, card: &GenericCard<'_>, variant: &Variant, target: Target<'_>) -> Series
+{
+    let mut series = Series { currency: Some(target.currency), ..Series::default() } ;
+    for date in prices.dates()
+    {
+        let Some(price) = prices.as_of(date).price(card, variant) else { continue } ;
+        let target = Target { date, ..target } ;
+        let mut conversion = target.conversion() ;
+        if !target.learn(&mut conversion, price.currency) { continue ; }
+        series.push(date, conversion.total([price]), &conversion) ;
+    }
+    series
+}
+
+/// Value of a collection in a currency at each snapshot date, with the exchange rates as of each date (see [`value`])<br/>
+/// Dates as of which none of its cards has a price, or one which converts to the currency, are left out
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::exchange::ExchangeRates;
+/// use mtg_multitool::history::collection_history;
+/// use mtg_multitool::money::Currency;
+/// use mtg_multitool::prices::PriceDb;
+/// use mtg_multitool::ltr;
+///
+/// let prices = PriceDb::from_csv("serial,price,currency,date\n\
+///                                 LTR C 0001,0.25,EUR,2026-08-01\n\
+///                                 LTR C 0001,0.50,EUR,2026-09-01\n\
+///                                 LTR R 0004,2.00,EUR,2026-09-01\n\
+///                                 LTR C 0001,0.20,EUR,2026-10-01\n").unwrap() ;
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4), ("LTR R 0004", 1)]).unwrap() ;
+///
+/// let rates = ExchangeRates::default() ;
+/// let history = collection_history(&collection, &prices, rates.to(Currency::try_from("EUR").unwrap(), Timestamp::now())) ;
+/// assert_eq!(history.points.iter().map(|(_, value)| value.to_string()).collect::<Vec<_>>(), vec!["1.00", "4.00", "2.80"]) ;
+/// assert_eq!(history.sparkline(), "▁█▅") ;
+/// ```
+pub fn collection_history(collection: &Collection<'_>, prices: &PriceDb, target: Target<'_>) -> Series
+{
+    let mut series = Series { currency: Some(target.currency), ..Series::default() } ;
+    for date in prices.dates()
+    {
+        let valuation = value(collection, &prices.as_of(date), Target { date, ..target }) ;
+        if valuation.items.iter().all(|item| item.price.is_none()) { continue ; }
+        series.push(date, valuation.total(), &valuation.conversion) ;
+    }
+    series
+}
+
+/// Change of the near mint price of a printing in a finish, between two dates
+#[derive(Debug, Clone)]
+pub struct Mover<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub finish: Finish,
+    /// Currency of both prices
+    pub currency: Currency,
+    pub before: Amount,
+    pub after: Amount,
+}
+
+impl Mover<'_>
+{
+    /// Gain (positive) or loss (negative) of a copy
+    pub fn change(&self) -> Amount
+    {
+        self.after - self.before
+    }
+}
+
+/// `Boromir, Warden of the Tower (LTR R 0004), foil: 2.00 → 3.50 EUR (+1.50)`
+impl std::fmt::Display for Mover<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let sign = if self.change() > Amount::default() { "+" } else { "" } ;
+        write!(f, "{}, {}: {} → {} ({}{})", self.card, self.finish.code(), self.before, Money::new(self.after, self.currency), sign, self.change())
+    }
+}
+
+/// Printings and finishes of a collection whose price changed between two dates, biggest changes first<br/>
+/// Printings not quoted as of both dates, or quoted in another currency as of each date, are left out
+pub fn movers<'gc>(collection: &Collection<'gc>, prices: &PriceDb, from: Timestamp, to: Timestamp) -> Vec<Mover<'gc>>
+{
+    let mut movers: Vec<Mover<'gc>> = vec![] ;
+    for (card, stock) in collection.stocks()
+    {
+        let Some(serial) = card.printing().serial() else { continue } ;
+        for (variant, _) in stock.iter()
+        {
+            if movers.iter().any(|mover| mover.finish == variant.finish && mover.card.same_printing(card)) { continue ; }
+            let quote = |date| prices.quote_at(serial, variant.finish, date) ;
+            let (Some(before), Some(after)) = (quote(from), quote(to)) else { continue } ;
+            if before.currency != after.currency { continue ; }
+            movers.push(Mover { card: card.clone(), finish: variant.finish, currency: after.currency, before: before.price, after: after.price }) ;
+        }
+    }
+    movers.retain(|mover| !mover.change().is_zero()) ;
+    movers.sort_by_key(|mover| std::cmp::Reverse(mover.change().abs())) ;
+    movers
+}
+
+/// A near mint price to be alerted about, when a printing in a finish crosses it
+#[derive(Debug, Clone)]
+pub struct Threshold<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub finish: Finish,
+    pub price: Money,
+}
+
+/// A threshold crossed between two dates, see [`crossings`]
+#[derive(Debug, Clone)]
+pub struct Crossing<'t, 'gc>
+{
+    pub threshold: &'t Threshold<'gc>,
+    /// In the currency of the threshold
+    pub before: Amount,
+    pub after: Amount,
+}
+
+impl Crossing<'_, '_>
+{
+    /// Whether the price rose to the threshold or above, rather than fell below it
+    pub fn is_rising(&self) -> bool
+    {
+        self.after >= self.threshold.price.amount
+    }
+}
+
+/// `Boromir, Warden of the Tower (LTR R 0004), foil rose to 3.00 EUR or above: 2.50 → 3.20`
+impl std::fmt::Display for Crossing<'_, '_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let threshold = self.threshold ;
+        let crossed = if self.is_rising() { "rose to" } else { "fell below" } ;
+        let above = if self.is_rising() { " or above" } else { "" } ;
+        write!(f, "{}, {} {} {}{}: {} → {}", threshold.card, threshold.finish.code(), crossed, threshold.price, above, self.before, self.after)
+    }
+}
+
+/// Thresholds crossed between the prices as of two dates, in threshold order<br/>
+/// Thresholds whose printing is not quoted in their currency as of both dates are never crossed
+pub fn crossings<'t, 'gc>(prices: &PriceDb, thresholds: &'t [Threshold<'gc>], from: Timestamp, to: Timestamp) -> Vec<Crossing<'t, 'gc>>
+{
+    thresholds.iter()
+        .filter_map(|threshold| {
+            let serial = threshold.card.printing().serial()? ;
+            let Money { amount: price, currency } = threshold.price ;
+            let quote = |date| prices.quote_at(serial, threshold.finish, date).filter(|quote| quote.currency == currency).map(|quote| quote.price) ;
+            let (before, after) = (quote(from)?, quote(to)?) ;
+            ((before >= price) != (after >= price)).then_some(Crossing { threshold, before, after })
+        })
+        .collect()
+}
+
+#[test]
+fn price_history()
+{
+    use crate::exchange::ExchangeRates ;
+
+    let catalog = crate::collections::test_catalog() ;
+    let (eur, usd) = (Currency::try_from("EUR").unwrap(), Currency::try_from("USD").unwrap()) ;
+    let card = |id| catalog.resolve(id, catalog.policy()).unwrap().clone() ;
+    let day = |month, day| Timestamp::from_date(2026, month, day).unwrap() ;
+
+    let mut prices = PriceDb::from_csv("serial,finish,price,currency,date\n\
+                                        LTR C 0001,,0.25,EUR,2026-08-01\n\
+                                        LTR R 0004,foil,2.50,EUR,2026-08-01\n\
+                                        LTR R 0004,,1.00,EUR,2026-08-01\n").unwrap() ;
+    prices.merge(PriceDb::from_csv("serial,finish,price,currency,date\n\
+                                    LTR C 0001,,0.30,EUR,2026-09-01\n\
+                                    LTR R 0004,foil,3.20,EUR,2026-09-01\n\
+                                    LTC R 0001,,4.00,EUR,2026-09-01\n").unwrap()) ;
+    // The shop moved to dollars for its last snapshot of LTC
+    prices.merge(PriceDb::from_csv("serial,finish,price,currency,date\n\
+                                    LTR R 0004,foil,2.90,EUR,2026-10-01\n\
+                                    LTR R 0004,,1.00,EUR,2026-10-01\n\
+                                    LTC R 0001,,3.75,USD,2026-10-01\n").unwrap()) ;
+    let rates = ExchangeRates::from_csv("date,from,to,rate\n2026-09-01,USD,EUR,0.90\n2026-10-01,USD,EUR,0.80\n").unwrap() ;
+    let euros = rates.to(eur, Timestamp::now()) ;
+    let prices = prices.with_multiplier(crate::Condition::Damaged, 50) ;
+
+    let foil = Variant { finish: Finish::Foil, ..Variant::default() } ;
+    let damaged = Variant { condition: crate::Condition::Damaged, ..Variant::default() } ;
+    // 3.75 USD as of October is 3.00 EUR
+    let history = card_history(&prices, &card("LTC R 0001"), &damaged, euros) ;
+    assert_eq!(history.points, vec![(day(9, 1), Amount::from_cents(200)), (day(10, 1), Amount::from_cents(150))]) ;
+    assert_eq!(history.rates.iter().map(|rate| rate.to_string()).collect::<Vec<_>>(), vec!["1 USD = 0.8 EUR (2026-10-01)"]) ;
+    assert_eq!(history.sparkline(), "█▁") ;
+    let mut csv = vec![] ;
+    history.write_csv(&mut csv).unwrap() ;
+    assert_eq!(String::from_utf8(csv).unwrap(), "Date,Value (EUR)\n2026-09-01,2.00\n2026-10-01,1.50\n") ;
+    assert_eq!(card_history(&prices, &card("LTC R 0001"), &damaged, ExchangeRates::default().to(eur, Timestamp::now())).points.len(), 1) ;
+    assert_eq!(card_history(&prices, &card("LTR R 0004"), &Variant::default(), euros).sparkline(), "▁▁▁") ;
+    assert_eq!(Series::default().sparkline(), "") ;
+
+    let mut collection = Collection::from_counts(&catalog, [("LTR C 0001", 4), ("LTR R 0004", 1), ("LTC R 0001", 1)]).unwrap() ;
+    collection.add_variant("LTR R 0004", foil, 2).unwrap() ;
+    collection.add_variant("LTR R 0004", damaged, 1).unwrap() ;
+    assert_eq!(collection_history(&collection, &prices, euros).points.iter().map(|(_, value)| *value).collect::<Vec<_>>(), [750, 1310, 1150].map(Amount::from_cents)) ;
+    // No rate to dollars as of August, 13.10 EUR as of September, 8.50 EUR and 3.75 USD as of October
+    let dollars = collection_history(&collection, &prices, rates.to(usd, Timestamp::now())) ;
+    assert_eq!(dollars.points.iter().map(|(date, value)| (*date, value.round(2))).collect::<Vec<_>>(), vec![
+        (day(9, 1), Amount::from_cents(1456)),
+        (day(10, 1), Amount::from_cents(1438)),
+    ]) ;
+    assert_eq!(dollars.rates.len(), 2) ;
+
+    assert_eq!(movers(&collection, &prices, day(8, 1), day(10, 1)).iter().map(Mover::to_string).collect::<Vec<_>>(), vec![
+        "Boromir, Warden of the Tower (LTR R 0004), foil: 2.50 → 2.90 EUR (+0.40)",
+        "Banish from Edoras (LTR C 0001), nonfoil: 0.25 → 0.30 EUR (+0.05)",
+    ]) ;
+    // LTC went from euros to dollars
+    assert_eq!(movers(&collection, &prices, day(9, 1), day(10, 1)).iter().map(|mover| mover.change()).collect::<Vec<_>>(), vec![Amount::from_cents(-30)]) ;
+
+    let thresholds = vec![
+        Threshold { card: card("LTR R 0004"), finish: Finish::Foil, price: Money::new(Amount::from_cents(300), eur) },
+        Threshold { card: card("LTC R 0001"), finish: Finish::Nonfoil, price: Money::new(Amount::from_cents(350), eur) },
+        Threshold { card: card("LTR C 0001"), finish: Finish::Nonfoil, price: Money::new(Amount::from_cents(100), eur) },
+    ] ;
+    assert_eq!(crossings(&prices, &thresholds, day(8, 1), day(9, 1)).iter().map(Crossing::to_string).collect::<Vec<_>>(), vec![
+        "Boromir, Warden of the Tower (LTR R 0004), foil rose to 3.00 EUR or above: 2.50 → 3.20",
+    ]) ;
+    assert_eq!(crossings(&prices, &thresholds, day(9, 1), day(10, 1)).iter().map(|crossing| (crossing.is_rising(), crossing.after)).collect::<Vec<_>>(), vec![
+        (false, Amount::from_cents(290)),
+    ]) ;
+}
@@ -29,19 +29,107 @@ impl<'a, T> AsId<'a> for T where T: Into<Id<'a>> + Clone
     }
 }
 
+impl<'any> Id<'any>
+{
+    /// The name part of this id, if any
+    pub fn name(&self) -> Option<&'any str>
+    {
+        use crate::ids::Id::* ;
+        match self
+        {
+            Name(n) | Both(n, _) | Either(n, _) => Some(n),
+            Serial(_) => None,
+        }
+    }
+
+    /// The serial part of this id, if any
+    pub fn serial(&self) -> Option<&'any str>
+    {
+        use crate::ids::Id::* ;
+        match self
+        {
+            Serial(id) | Both(_, id) | Either(_, id) => Some(id),
+            Name(_) => None,
+        }
+    }
+
+    /// Copies this id into an id owning its strings
+    pub fn to_owned_id(&self) -> OwnedId
+    {
+        match *self
+        {
+            Id::Name(name) => OwnedId::Name(name.to_string()),
+            Id::Serial(serial) => OwnedId::Serial(serial.to_string()),
+            Id::Both(name, serial) => OwnedId::Both(name.to_string(), serial.to_string()),
+            Id::Either(name, serial) => OwnedId::Either(name.to_string(), serial.to_string()),
+        }
+    }
+}
+
+/// An id owning its strings, for ids read at runtime and outliving their source
+/// ```
+/// use mtg_multitool::ids::{Id, OwnedId};
+/// let id = OwnedId::Both("Banish from Edoras".to_string(), "LTR C 0001".to_string()) ;
+/// assert_eq!(Id::from(&id), Id::Serial("LTR C 1")) ;
+/// assert_eq!(Id::Name("Banish from Edoras").to_owned_id(), OwnedId::Name("Banish from Edoras".to_string())) ;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnedId
+{
+    Name(String),
+    Serial(String),
+    Both(String, String),
+    Either(String, String),
+}
+
+impl<'a> From<&'a OwnedId> for Id<'a>
+{
+    fn from(value: &'a OwnedId) -> Self
+    {
+        match value
+        {
+            OwnedId::Name(name) => Id::Name(name),
+            OwnedId::Serial(serial) => Id::Serial(serial),
+            OwnedId::Both(name, serial) => Id::Both(name, serial),
+            OwnedId::Either(name, serial) => Id::Either(name, serial),
+        }
+    }
+}
+
+/// Names are compared once normalized (see [`crate::names::normalize`]), serials by their components<br/>
+/// An `Either` matches as soon as one of its parts matches, other ids need every part they share to match
+/// ```
+/// use mtg_multitool::ids::Id::* ;
+/// assert_eq!(Name("Éowyn, Fearless Knight"), Name("éowyn,  fearless knight")) ;
+/// assert_eq!(Name("Banish from Edoras"), Both("Banish from Edoras", "LTR C 0001")) ;
+/// assert_eq!(Serial("ltr c 1"), Both("Banish from Edoras", "LTR C 0001")) ;
+/// assert_ne!(Name("Banish from Edoras"), Serial("LTR C 0001")) ;
+/// ```
 impl PartialEq for Id<'_>
 {
     fn eq(&self, other: &Self) -> bool {
         use crate::ids::Id::* ;
-        match (self, other)
+
+        let names = match (self.name(), other.name())
         {
-            (Both(n, id), Both(nn, idd)) => n.to_ascii_lowercase() == nn.to_ascii_lowercase() && is_serial(id).unwrap() == is_serial(idd).unwrap(),
-            (Either(n, id), Either(nn, idd)) => n.to_ascii_lowercase() == nn.to_ascii_lowercase() || is_serial(id).unwrap() == is_serial(idd).unwrap(),
+            (Some(n), Some(nn)) => Some(crate::names::same_name(n, nn)),
+            _ => None,
+        } ;
+        let serials = match (self.serial(), other.serial())
+        {
+            (Some(id), Some(idd)) => Some(same_serial(id, idd)),
+            _ => None,
+        } ;
 
-            (Name(n) | Either(n, _), Name(nn) | Either(nn, _)) => n.to_ascii_lowercase() == nn.to_ascii_lowercase(),
-            (Serial(id) | Either(_, id), Serial(idd) | Either(_, idd)) => is_serial(id).unwrap() == is_serial(idd).unwrap(),
+        match (self, other)
+        {
+            (Either(..), _) | (_, Either(..)) => names == Some(true) || serials == Some(true),
 
-            _ => false
+            _ => match (names, serials)
+            {
+                (None, None) => false,
+                (names, serials) => names.unwrap_or(true) && serials.unwrap_or(true),
+            }
         }
     }
 }
@@ -51,13 +139,173 @@ fn id_partialeq()
 {
     use crate::ids::Id::{*} ;
     assert_eq!(Name("Banish from Edoras"), Both("BaNISH FROm EdORas", "LTR C 0001")) ;
-    todo!()
+    assert_eq!(Name("Éowyn, Fearless Knight"), Name("ÉOWYN, FEARLESS KNIGHT")) ;
+    assert_eq!(Name("Gríma’s  Whisper"), Name("Grima's Whisper")) ;
+    assert_eq!(Name("Sword — Ring"), Name("sword - ring")) ;
+    assert_ne!(Name("Banish from Edoras"), Name("The Battle of Bywater")) ;
+
+    assert_eq!(Serial("LTR C 0001"), Serial("ltr c 1")) ;
+    assert_ne!(Serial("LTR C 0001"), Serial("LTR C 0002")) ;
+    assert_ne!(Serial("LTR C 0001"), Serial("Banish from Edoras")) ;
+    assert_ne!(Name("Banish from Edoras"), Serial("LTR C 0001")) ;
+
+    assert_eq!(Both("Banish from Edoras", "LTR C 0001"), Both("banish from edoras", "LTR C 1")) ;
+    assert_ne!(Both("Banish from Edoras", "LTR C 0001"), Both("Banish from Edoras", "LTR C 0002")) ;
+    assert_eq!(Serial("LTR C 0001"), Both("Banish from Edoras", "LTR C 0001")) ;
+
+    assert_eq!(Either("Banish from Edoras", "LTR C 0002"), Both("Banish from Edoras", "LTR C 0001")) ;
+    assert_eq!(Either("Whatever", "LTR C 0001"), Both("Banish from Edoras", "LTR C 0001")) ;
+    assert_ne!(Either("Whatever", "LTR C 0002"), Both("Banish from Edoras", "LTR C 0001")) ;
+    assert_eq!(Either("Banish from Edoras", "LTR C 0001"), Name("Banish from Edoras")) ;
+}
 
+/// Compares two serials by their components, falling back on a normalized comparison if both are malformed<br/>
+/// A serial without rarity (`"LTR 0001"`, `"LTR-0001"`) matches any rarity
+fn same_serial(a: &str, b: &str) -> bool
+{
+    match (parse_serial(a), parse_serial(b))
+    {
+        (Some((series, rarity, id)), Some((series2, rarity2, id2))) => {
+            series == series2 && id == id2 && match (rarity, rarity2)
+            {
+                (Some(rarity), Some(rarity2)) => rarity == rarity2,
+                _ => true,
+            }
+        },
+        (None, None) => crate::names::same_name(a, b),
+        _ => false,
+    }
+}
+
+/// How to pick a card when an id matches several of them (such as reprints sharing a name)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Policy
+{
+    /// Never guess: several matches are an error
+    #[default]
+    Strict,
+    /// Picks the newest printing, candidates being expected to be listed from the oldest to the newest
+    PreferNewest,
+    /// Picks the printing from the given set (example: `PreferSet("LTR".to_string())`)
+    PreferSet(String),
+}
+
+/// Why an id could not be resolved to exactly one card
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError<'c>
+{
+    /// No card matches the id
+    NotFound,
+    /// Several cards match the id, and the policy could not choose between them
+    Ambiguous(Vec<Id<'c>>),
+}
+
+impl std::fmt::Display for ResolveError<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            ResolveError::NotFound => write!(f, "No card matches this id"),
+            ResolveError::Ambiguous(candidates) => write!(f, "Several cards match this id: {:?}", candidates),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError<'_> {}
+
+/// Resolves an id against candidates, returning the index of the only (or chosen) matching candidate<br/>
+/// An `Either` whose name and serial point at different cards is ambiguous whatever the policy, as it is contradictory rather than a reprint
+/// ```
+/// use mtg_multitool::ids::{resolve, Id::*, Policy, ResolveError};
+/// let catalog = [Both("Gandalf", "LTR R 0010"), Both("Gandalf", "LTC R 0020"), Both("Frodo", "LTR C 0011")] ;
+///
+/// assert_eq!(resolve(Name("Frodo"), catalog, &Policy::Strict), Ok(2)) ;
+/// assert_eq!(resolve(Name("Sam"), catalog, &Policy::Strict), Err(ResolveError::NotFound)) ;
+/// assert_eq!(resolve(Name("Gandalf"), catalog, &Policy::Strict), Err(ResolveError::Ambiguous(vec![catalog[0], catalog[1]]))) ;
+/// assert_eq!(resolve(Name("Gandalf"), catalog, &Policy::PreferNewest), Ok(1)) ;
+/// assert_eq!(resolve(Name("Gandalf"), catalog, &Policy::PreferSet("ltr".to_string())), Ok(0)) ;
+/// ```
+pub fn resolve<'c, I>(id: Id<'_>, candidates: I, policy: &Policy) -> Result<usize, ResolveError<'c>>
+    where I: IntoIterator<Item = Id<'c>>
+{
+    let matches: Vec<(usize, Id<'c>)> = candidates.into_iter()
+        .enumerate()
+        .filter(|(_, candidate)| *candidate == id)
+        .collect() ;
+
+    match matches.as_slice()
+    {
+        [] => return Err(ResolveError::NotFound),
+        [(index, _)] => return Ok(*index),
+        _ => (),
+    }
+
+    // An `Either` whose name and serial point at different cards is contradictory, not a reprint: no policy settles it
+    let contradictory = match id
+    {
+        Id::Either(name, serial) => {
+            let by_name = |candidate: &Id<'_>| candidate.name().is_some_and(|n| crate::names::same_name(n, name)) ;
+            let by_serial = |candidate: &Id<'_>| candidate.serial().is_some_and(|id| same_serial(id, serial)) ;
+            matches.iter().any(|(_, candidate)| by_name(candidate) && !by_serial(candidate))
+                && matches.iter().any(|(_, candidate)| by_serial(candidate) && !by_name(candidate))
+        },
+        _ => false,
+    } ;
+
+    match policy
+    {
+        _ if contradictory => (),
+        Policy::Strict => (),
+        Policy::PreferNewest => return Ok(matches.last().unwrap().0),
+        Policy::PreferSet(set) => {
+            let set = set.trim().to_ascii_uppercase() ;
+            let in_set: Vec<usize> = matches.iter()
+                .filter(|(_, candidate)| candidate.serial().and_then(parse_serial).is_some_and(|(series, _, _)| series == set))
+                .map(|(index, _)| *index)
+                .collect() ;
+
+            if let [index] = in_set.as_slice()
+            {
+                return Ok(*index) ;
+            }
+        },
+    }
+
+    Err(ResolveError::Ambiguous(matches.into_iter().map(|(_, candidate)| candidate).collect()))
+}
+
+#[test]
+fn resolve_tests()
+{
+    use crate::ids::Id::{*} ;
+    let catalog = [
+        Both("Banish from Edoras", "LTR C 0001"),
+        Both("The Battle of Bywater", "LTR R 0002"),
+        Both("Banish from Edoras", "LTC C 0001"),
+    ] ;
+
+    assert_eq!(resolve(Name("The Battle of Bywater"), catalog, &Policy::Strict), Ok(1)) ;
+    assert_eq!(resolve(Serial("LTC C 1"), catalog, &Policy::Strict), Ok(2)) ;
+    assert_eq!(resolve(Both("Banish from Edoras", "LTR C 0001"), catalog, &Policy::Strict), Ok(0)) ;
+    assert_eq!(resolve(Name("Dawn of a New Age"), catalog, &Policy::PreferNewest), Err(ResolveError::NotFound)) ;
+
+    assert_eq!(resolve(Name("banish from edoras"), catalog, &Policy::Strict), Err(ResolveError::Ambiguous(vec![catalog[0], catalog[2]]))) ;
+    assert_eq!(resolve(Name("banish from edoras"), catalog, &Policy::PreferNewest), Ok(2)) ;
+    assert_eq!(resolve(Name("banish from edoras"), catalog, &Policy::PreferSet("LTR".to_string())), Ok(0)) ;
+    assert!(resolve(Name("banish from edoras"), catalog, &Policy::PreferSet("MOM".to_string())).is_err()) ;
+
+    // Either pointing at two different cards
+    assert_eq!(resolve(Either("The Battle of Bywater", "LTR C 0001"), catalog, &Policy::Strict), Err(ResolveError::Ambiguous(vec![catalog[0], catalog[1]]))) ;
+    assert_eq!(resolve(Either("The Battle of Bywater", "LTR C 0001"), catalog, &Policy::PreferNewest), Err(ResolveError::Ambiguous(vec![catalog[0], catalog[1]]))) ;
+    assert!(resolve(Either("The Battle of Bywater", "LTR C 0001"), catalog, &Policy::PreferSet("LTR".to_string())).is_err()) ;
+    // Either whose serial matches nothing is a reprinted name
+    assert_eq!(resolve(Either("Banish from Edoras", "MOM C 0001"), catalog, &Policy::PreferNewest), Ok(2)) ;
 }
 
 /// Checks if given `&str` is formatted as a Serial would be ;<br/>
 /// Returns `Some((series, rarity, id_within_series))` if it is, `None` otherwise
-fn is_serial(s: &str) -> Option<(String, Rarity, usize)>
+pub(crate) fn is_serial(s: &str) -> Option<(String, Rarity, usize)>
 {
     let mut ss = s.split(" ") ;
     if let Some(series) = ss.next()
@@ -98,6 +346,53 @@ fn is_serial_tests()
     assert_eq!(is_serial("LTR f 01"), None) ;
 }
 
+/// Like [`is_serial`], but also accepts serials without rarity, such as `"LTR 0001"` or `"LTR-0001"`;<br/>
+/// Returns `Some((series, rarity, id_within_series))` if it is, `None` otherwise
+pub(crate) fn parse_serial(s: &str) -> Option<(String, Option<Rarity>, usize)>
+{
+    if let Some((series, rarity, id)) = is_serial(s)
+    {
+        return Some((series, Some(rarity), id)) ;
+    }
+
+    let (series, id) = s.trim().split_once([' ', '-'])? ;
+    let series = series.trim() ;
+    if is_series(series)
+    {
+        if let Ok(id) = id.trim().parse::<usize>()
+        {
+            return Some((series.to_ascii_uppercase(), None, id)) ;
+        }
+    }
+
+    None
+}
+
+/// Checks if given `&str` looks like a set code (`"LTR"`, `"10E"`, `"PLTR"`)
+pub(crate) fn is_series(s: &str) -> bool
+{
+    (2..=6).contains(&s.len())
+        && s.chars().all(|c| c.is_ascii_alphanumeric())
+        && s.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+#[test]
+fn parse_serial_tests()
+{
+    use crate::Rarity::* ;
+    assert_eq!(parse_serial("LTR C 0001"), Some(("LTR".to_string(), Some(Common), 1))) ;
+    assert_eq!(parse_serial("LTR 0001"), Some(("LTR".to_string(), None, 1))) ;
+    assert_eq!(parse_serial("ltr-4"), Some(("LTR".to_string(), None, 4))) ;
+    assert_eq!(parse_serial("10E 12"), Some(("10E".to_string(), None, 12))) ;
+    assert_eq!(parse_serial("LTR"), None) ;
+    assert_eq!(parse_serial("123 4"), None) ;
+    assert_eq!(parse_serial("Banish from Edoras"), None) ;
+
+    assert!(same_serial("LTR C 0001", "LTR-1")) ;
+    assert!(!same_serial("LTR R 0001", "LTR C 1")) ;
+    assert!(!same_serial("LTR 0001", "LTC 1")) ;
+}
+
 impl<'s> From<&'s str> for Id<'s>
 {
     fn from(value: &'s str) -> Self {
@@ -115,5 +410,10 @@ impl<'s> From<&'s str> for Id<'s>
 #[test]
 fn id_from_str()
 {
-    todo!()
+    use crate::ids::Id::{*} ;
+    assert!(matches!(Id::from("LTR C 0001"), Serial("LTR C 0001"))) ;
+    assert!(matches!(Id::from("ltr c 1"), Serial("ltr c 1"))) ;
+    assert!(matches!(Id::from("Banish from Edoras"), Name("Banish from Edoras"))) ;
+    assert!(matches!(Id::from("Éowyn, Fearless Knight"), Name(_))) ;
+    assert!(matches!(Id::from("LTR 1"), Name("LTR 1"))) ;
 }
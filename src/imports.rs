@@ -0,0 +1,475 @@
+use crate::collections::Collection;
+use crate::ids::{AsId, Id, Policy, ResolveError};
+use crate::references::Reference;
+use crate::stock::Variant;
+use crate::{Condition, Finish, Language};
+
+/// Inventory exports which can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format
+{
+    /// Moxfield collection csv (`Count,Tradelist Count,Name,Edition,...,Collector Number,...`)
+    Moxfield,
+    /// Deckbox inventory csv (`Count,Tradelist Count,Name,Edition,Edition Code,Card Number,...`)
+    Deckbox,
+    /// Archidekt collection csv (`Quantity,Name,...,Edition Code,...,Collector Number`)
+    Archidekt,
+    /// MTG Arena text, one `4 Banish from Edoras (LTR) 1` card per line, followed by `*F*` for foils or `*E*` for etched foils
+    Arena,
+    /// Delver Lens (and other scanner apps) csv (`Name,Edition code,Collector's number,Quantity,...`)
+    DelverLens,
+}
+
+/// Header names of the columns a csv format is read from, compared once normalized
+struct Columns
+{
+    count: &'static [&'static str],
+    name: &'static [&'static str],
+    set: &'static [&'static str],
+    number: &'static [&'static str],
+    finish: &'static [&'static str],
+    condition: &'static [&'static str],
+    language: &'static [&'static str],
+}
+
+impl Format
+{
+    fn columns(&self) -> Option<Columns>
+    {
+        match self
+        {
+            Format::Moxfield => Some(Columns
+            {
+                count: &["Count"], name: &["Name"], set: &["Edition"], number: &["Collector Number"],
+                finish: &["Foil"], condition: &["Condition"], language: &["Language"],
+            }),
+            Format::Deckbox => Some(Columns
+            {
+                count: &["Count"], name: &["Name"], set: &["Edition Code"], number: &["Card Number"],
+                finish: &["Foil"], condition: &["Condition"], language: &["Language"],
+            }),
+            Format::Archidekt => Some(Columns
+            {
+                count: &["Quantity"], name: &["Name"], set: &["Edition Code"], number: &["Collector Number"],
+                finish: &["Finish"], condition: &["Condition"], language: &["Language"],
+            }),
+            Format::DelverLens => Some(Columns
+            {
+                count: &["Quantity", "Count", "Qty"],
+                name: &["Name", "Card Name"],
+                set: &["Edition code", "Set code", "Set"],
+                number: &["Collector's number", "Collector Number", "Number"],
+                finish: &["Foil", "Finish"],
+                condition: &["Condition"],
+                language: &["Language"],
+            }),
+            Format::Arena => None,
+        }
+    }
+}
+
+/// Imports an inventory export, resolving its rows against a catalog (such as `ltr::collection()`)<br/>
+/// Rows are resolved with their set code and collector number when given, by name otherwise
+/// (with [`Policy::PreferSet`] if only the set code is known).
+/// Their finish, condition and language columns, when found, give the copies' [`Variant`] (see [`Variant::of`] for missing ones).
+/// Rows which do not resolve to exactly one card are left out, and listed in the report with suggestions.
+/// ```
+/// use mtg_multitool::imports::{import, Format};
+/// use mtg_multitool::ltr;
+///
+/// let export = "Count,Tradelist Count,Name,Edition,Condition,Language,Foil,Tags,Last Modified,Collector Number\n\
+///               2,0,\"Boromir, Warden of the Tower\",ltr,Near Mint,English,,,2023-07-01,4\n\
+///               1,0,Bill the Pony,ltr,Near Mint,English,,,2023-07-01,\n" ;
+/// let (collection, report) = import(&ltr::collection(), Format::Moxfield, export).unwrap() ;
+///
+/// assert_eq!(collection.count("LTR R 0004"), Some(2)) ;
+/// assert_eq!(report.unmatched[0].name.as_deref(), Some("Bill the Pony")) ;
+/// assert_eq!(report.unmatched[0].suggestions[0].name(), Some("Bill the Poney")) ;
+/// ```
+pub fn import<'gc>(catalog: &Collection<'gc>, format: Format, text: &str) -> Result<(Collection<'gc>, ImportReport<'gc>), ImportError>
+{
+    let rows = match format.columns()
+    {
+        Some(columns) => csv_rows(text, &columns)?,
+        None => arena_rows(text),
+    } ;
+
+    let mut collection = Collection::from_counts(catalog, std::iter::empty::<(&str, usize)>())
+        .expect("An empty list of counts always resolves") ;
+    let mut report = ImportReport::default() ;
+
+    for row in rows
+    {
+        let resolved = row.count.clone()
+            .and_then(|count| row.variant.clone().map(|variant| (count, variant)))
+            .and_then(|(count, variant)| row.number.clone().map(|number| (count, variant, number)))
+            .and_then(|(count, variant, number)| resolve_row(&collection, &row, number).map(|id| (id, count, variant)).map_err(|e| e.to_string())) ;
+        match resolved
+        {
+            Ok((id, count, mut variant)) => {
+                if !row.has_language
+                {
+                    variant.language = *collection.resolve(id, collection.policy()).expect("Ids of the collection's own cards resolve").printing().language() ;
+                }
+                collection.add_variant(id, variant, count).expect("Ids of the collection's own cards resolve") ;
+                report.imported += 1 ;
+            },
+            Err(reason) => {
+                let suggestions = suggestions(catalog, &row) ;
+                report.unmatched.push(UnmatchedRow
+                {
+                    line: row.line,
+                    name: row.name,
+                    set: row.set,
+                    number: row.number.unwrap_or(None),
+                    count: row.count.unwrap_or(0),
+                    reason,
+                    suggestions,
+                }) ;
+            },
+        }
+    }
+
+    Ok((collection, report))
+}
+
+/// A row of an export, before resolution
+struct Row
+{
+    line: usize,
+    name: Option<String>,
+    set: Option<String>,
+    number: Result<Option<usize>, String>,
+    count: Result<usize, String>,
+    variant: Result<Variant, String>,
+    /// Whether the language of the copies is given, rather than being their printing's
+    has_language: bool,
+}
+
+fn csv_rows(text: &str, columns: &Columns) -> Result<Vec<Row>, ImportError>
+{
+    let mut records = crate::csv::records(text, ',')
+        .map_err(|(line, reason)| ImportError::Malformed { line, reason })?
+        .into_iter() ;
+    let (_, header) = records.next().ok_or(ImportError::MissingColumn(columns.name[0]))? ;
+
+    let find = |aliases: &[&str]| header.iter().position(|title| aliases.iter().any(|alias| crate::names::same_name(title, alias))) ;
+    let name = find(columns.name).ok_or(ImportError::MissingColumn(columns.name[0]))? ;
+    let (count, set, number) = (find(columns.count), find(columns.set), find(columns.number)) ;
+    let (finish, condition, language) = (find(columns.finish), find(columns.condition), find(columns.language)) ;
+
+    let field = |fields: &[String], index: Option<usize>| index
+        .and_then(|index| fields.get(index))
+        .map(|field| field.trim().to_string())
+        .filter(|field| !field.is_empty()) ;
+
+    Ok(records.map(|(line, fields)| Row
+    {
+        line,
+        name: field(&fields, Some(name)),
+        set: field(&fields, set).map(|set| set.to_ascii_uppercase()),
+        number: field(&fields, number).map(|number| parse_number(&number)).transpose(),
+        count: match field(&fields, count)
+        {
+            None => Ok(1),
+            Some(count) => count.parse().map_err(|_| format!("Could not parse count `{}`", count)),
+        },
+        variant: parse_variant(field(&fields, finish), field(&fields, condition), field(&fields, language)),
+        has_language: field(&fields, language).is_some(),
+    }).collect())
+}
+
+/// Missing fields are those of the default variant
+fn parse_variant(finish: Option<String>, condition: Option<String>, language: Option<String>) -> Result<Variant, String>
+{
+    let describe = |field: Option<String>, error: &str| format!("{} `{}`", error, field.unwrap_or_default()) ;
+    Ok(Variant
+    {
+        finish: Finish::try_from(finish.as_deref().unwrap_or_default()).map_err(|e| describe(finish.clone(), e))?,
+        condition: Condition::try_from(condition.as_deref().unwrap_or_default()).map_err(|e| describe(condition.clone(), e))?,
+        language: match language.as_deref()
+        {
+            None => Language::default(),
+            Some(name) => Language::try_from(name).map_err(|e| describe(language.clone(), e))?,
+        },
+    })
+}
+
+/// Collector numbers such as `"0004"`<br/>
+/// Suffixed or prefixed numbers (`"4a"`, `"★1"`) are other cards than the plain number, which serials cannot tell apart
+fn parse_number(number: &str) -> Result<usize, String>
+{
+    let error = || format!("Could not parse collector number `{}`", number) ;
+    if !number.chars().all(|c| c.is_ascii_digit()) { return Err(error()) ; }
+    number.parse().map_err(|_| error())
+}
+
+/// Section titles found in Arena exports, which are not cards
+const ARENA_SECTIONS: [&str ; 6] = ["Deck", "Sideboard", "Commander", "Companion", "Maybeboard", "About"] ;
+
+fn arena_rows(text: &str) -> Vec<Row>
+{
+    // The `About` section holds the deck's `Name`, which is not a card
+    let mut about = false ;
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("//"))
+        .filter(|(_, line)| match ARENA_SECTIONS.iter().find(|section| section.eq_ignore_ascii_case(line))
+        {
+            Some(section) => {
+                about = *section == "About" ;
+                false
+            },
+            None => !(about && line.starts_with("Name ")),
+        })
+        .map(|(line, content)| {
+            let (content, finish) = split_finish(content) ;
+            match Reference::try_from(content)
+            {
+                Ok(reference) => Row
+                {
+                    line,
+                    name: reference.name().map(str::to_string),
+                    set: reference.set().map(str::to_ascii_uppercase),
+                    number: Ok(reference.number()),
+                    count: Ok(reference.quantity),
+                    variant: Ok(Variant { finish, ..Variant::default() }),
+                    has_language: false,
+                },
+                Err(reason) => Row { line, name: Some(content.to_string()), set: None, number: Ok(None), count: Err(reason.to_string()), variant: Ok(Variant::default()), has_language: false },
+            }
+        })
+        .collect()
+}
+
+/// Splits the `*F*` (foil) or `*E*` (etched) marker off the end of a text line
+fn split_finish(line: &str) -> (&str, Finish)
+{
+    match (line.strip_suffix("*F*"), line.strip_suffix("*E*"))
+    {
+        (Some(rest), _) => (rest.trim_end(), Finish::Foil),
+        (_, Some(rest)) => (rest.trim_end(), Finish::Etched),
+        _ => (line, Finish::Nonfoil),
+    }
+}
+
+/// Resolves a row to one of the collection's cards, and returns that card's id
+fn resolve_row<'gc>(collection: &Collection<'gc>, row: &Row, number: Option<usize>) -> Result<Id<'gc>, ResolveError<'gc>>
+{
+    let card = match (row.name.as_deref(), row.set.as_deref(), number)
+    {
+        (name, Some(set), Some(number)) => {
+            let serial = format!("{} {:04}", set, number) ;
+            match name
+            {
+                Some(name) => collection.resolve(Id::Both(name, &serial), collection.policy())?,
+                None => collection.resolve(Id::Serial(&serial), collection.policy())?,
+            }
+        },
+        (Some(name), Some(set), None) => collection.resolve(Id::Name(name), &Policy::PreferSet(set.to_string()))?,
+        (Some(name), None, _) => collection.resolve(Id::Name(name), collection.policy())?,
+        (None, _, _) => return Err(ResolveError::NotFound),
+    } ;
+
+    Ok(card.as_id())
+}
+
+/// Most suggestions given for an unmatched row
+const MAX_SUGGESTIONS: usize = 3 ;
+
+/// The catalog's cards whose name is the closest to the row's name, closest first
+fn suggestions<'gc>(catalog: &Collection<'gc>, row: &Row) -> Vec<Id<'gc>>
+{
+    let Some(name) = row.name.as_deref() else { return vec![] } ;
+    let tolerance = (name.chars().count() / 4).max(2) ;
+
+    let mut close: Vec<(usize, Id<'gc>)> = catalog.iter()
+        .map(|(card, _)| (crate::names::distance(name, card.name()), card.as_id()))
+        .filter(|(distance, _)| *distance <= tolerance)
+        .collect() ;
+    close.sort_by_key(|(distance, _)| *distance) ;
+    close.into_iter().take(MAX_SUGGESTIONS).map(|(_, id)| id).collect()
+}
+
+/// What happened while importing an export
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport<'gc>
+{
+    /// Number of rows added to the collection
+    pub imported: usize,
+    /// Rows which were left out
+    pub unmatched: Vec<UnmatchedRow<'gc>>,
+}
+
+impl ImportReport<'_>
+{
+    /// Whether every row of the export was imported
+    pub fn is_complete(&self) -> bool
+    {
+        self.unmatched.is_empty()
+    }
+}
+
+/// One line per unmatched row, such as `Line 3: 1 Bill the Pony [LTR]: No card matches this id (did you mean Bill the Poney?)`
+impl std::fmt::Display for ImportReport<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        writeln!(f, "{} rows imported, {} unmatched", self.imported, self.unmatched.len())? ;
+        for row in &self.unmatched
+        {
+            write!(f, "Line {}: {} {}", row.line, row.count, row.name.as_deref().unwrap_or("?"))? ;
+            match (&row.set, row.number)
+            {
+                (Some(set), Some(number)) => write!(f, " [{} {}]", set, number)?,
+                (Some(set), None) => write!(f, " [{}]", set)?,
+                _ => (),
+            }
+            write!(f, ": {}", row.reason)? ;
+
+            let suggestions: Vec<String> = row.suggestions.iter()
+                .map(|id| match (id.name(), id.serial())
+                {
+                    (Some(name), Some(serial)) => format!("{} ({})", name, serial),
+                    (name, serial) => name.or(serial).unwrap_or_default().to_string(),
+                })
+                .collect() ;
+            if !suggestions.is_empty()
+            {
+                write!(f, " (did you mean {}?)", suggestions.join(", "))? ;
+            }
+            writeln!(f)? ;
+        }
+        Ok(())
+    }
+}
+
+/// A row of an export which does not resolve to a card of the catalog
+#[derive(Debug, Clone)]
+pub struct UnmatchedRow<'gc>
+{
+    /// Line number, starting at 1
+    pub line: usize,
+    pub name: Option<String>,
+    pub set: Option<String>,
+    pub number: Option<usize>,
+    pub count: usize,
+    pub reason: String,
+    /// Closest cards of the catalog, closest first
+    pub suggestions: Vec<Id<'gc>>,
+}
+
+/// Why an export could not be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportError
+{
+    /// The csv header has no column for the card names
+    MissingColumn(&'static str),
+    /// The csv text is not well formed
+    Malformed { line: usize, reason: &'static str },
+}
+
+impl std::fmt::Display for ImportError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            ImportError::MissingColumn(column) => write!(f, "The export has no `{}` column", column),
+            ImportError::Malformed { line, reason } => write!(f, "Line {}: {}", line, reason),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+#[test]
+fn import_csv_formats()
+{
+    let catalog = crate::ltr::collection() ;
+
+    let deckbox = "Count,Tradelist Count,Name,Edition,Edition Code,Card Number,Condition,Language,Foil\n\
+                   3,0,Banish from Edoras,The Lord of the Rings: Tales of Middle-earth,LTR,1,Near Mint,English,\n\
+                   1,0,\"Boromir, Warden of the Tower\",The Lord of the Rings: Tales of Middle-earth,,,Near Mint,English,foil\n" ;
+    let (collection, report) = import(&catalog, Format::Deckbox, deckbox).unwrap() ;
+    assert!(report.is_complete()) ;
+    assert_eq!(report.imported, 2) ;
+    assert_eq!(collection.count("Banish from Edoras"), Some(3)) ;
+    assert_eq!(collection.count("LTR R 0004"), Some(1)) ;
+    assert_eq!(collection.count_variant("LTR R 0004", &Variant { finish: Finish::Foil, ..Variant::default() }), Some(1)) ;
+
+    let archidekt = "Quantity,Name,Finish,Condition,Date Added,Language,Purchase Price,Tags,Edition Name,Edition Code,Multiverse Id,Scryfall ID,MTGO ID,Collector Number\n\
+                     2,Bill the Poney,Normal,NM,2023-07-01,EN,,,Tales of Middle-earth,ltr,,,,3\n\
+                     1,The Battle of Bywater,Foil,NM,2023-07-01,EN,,,Tales of Middle-earth,ltr,,,,2\n" ;
+    let (collection, report) = import(&catalog, Format::Archidekt, archidekt).unwrap() ;
+    assert!(report.is_complete()) ;
+    assert_eq!(collection.total(), 3) ;
+    assert_eq!(collection.count("LTR R 0002"), Some(1)) ;
+    assert_eq!(collection.count_variant("LTR R 0002", &Variant { finish: Finish::Foil, ..Variant::default() }), Some(1)) ;
+
+    let delver = "Name,Edition code,Collector's number,QUANTITY,Foil,Condition,Language\n\
+                  \"Dawn of a New Age\",LTR,5,1,Foil,Lightly Played,French\n\
+                  Banish from Edoras,,,2,,,\n\
+                  Bill the Poney,LTR,3,1,,Mint-ish,\n" ;
+    let (collection, report) = import(&catalog, Format::DelverLens, delver).unwrap() ;
+    assert_eq!(collection.count_variant("LTR M 0005", &Variant::new(Finish::Foil, Condition::LightlyPlayed, Language::French)), Some(1)) ;
+    assert_eq!(collection.count_variant("Banish from Edoras", &Variant::default()), Some(2)) ;
+    assert_eq!(report.unmatched[0].reason, "Could not parse into condition `Mint-ish`") ;
+
+    let delver = "Name,Edition code,Collector's number,QUANTITY,Foil\n\
+                  \"Dawn of a New Age\",LTR,5,1,\n\
+                  Banish from Edoras,,,2,\n" ;
+    let (collection, report) = import(&catalog, Format::DelverLens, delver).unwrap() ;
+    assert!(report.is_complete()) ;
+    assert_eq!(collection.total(), 3) ;
+
+    let moxfield = "Count,Tradelist Count,Name,Edition,Collector Number\n\
+                    1,0,Banish from Edoras,ltr,2\n\
+                    two,0,Bill the Poney,ltr,3\n\
+                    1,0,Boromir Warden of the Tower,ltr,\n\
+                    1,0,Gandalf the Grey,ltr,\n" ;
+    let (collection, report) = import(&catalog, Format::Moxfield, moxfield).unwrap() ;
+    assert_eq!(collection.total(), 0) ;
+    assert_eq!(report.unmatched.iter().map(|row| row.line).collect::<Vec<_>>(), vec![2, 3, 4, 5]) ;
+    // The name is known, but not at that collector number
+    assert_eq!(report.unmatched[0].suggestions[0], Id::Serial("LTR C 0001")) ;
+    assert_eq!(report.unmatched[1].reason, "Could not parse count `two`") ;
+    assert_eq!(report.unmatched[2].suggestions[0].serial(), Some("LTR R 0004")) ;
+    assert!(report.unmatched[3].suggestions.is_empty()) ;
+    assert!(report.to_string().starts_with("0 rows imported, 4 unmatched\nLine 2: 1 Banish from Edoras [LTR 2]: No card matches this id (did you mean Banish from Edoras (LTR C 0001)?)\n")) ;
+
+    // Suffixed collector numbers are other printings, which the catalog does not know
+    let moxfield = "Count,Tradelist Count,Name,Edition,Collector Number\n1,0,\"Boromir, Warden of the Tower\",ltr,4a\n1,0,Banish from Edoras,ltr,1\n" ;
+    let (collection, report) = import(&catalog, Format::Moxfield, moxfield).unwrap() ;
+    assert_eq!(collection.count("LTR R 0004"), Some(0)) ;
+    assert_eq!((report.imported, report.unmatched[0].line, report.unmatched[0].number), (1, 2, None)) ;
+    assert_eq!(report.unmatched[0].reason, "Could not parse collector number `4a`") ;
+    let deckbox = "Count,Tradelist Count,Name,Edition,Edition Code,Card Number\n2,0,Bill the Poney,,LTR,3★\n" ;
+    let (collection, report) = import(&catalog, Format::Deckbox, deckbox).unwrap() ;
+    assert_eq!((collection.total(), report.unmatched[0].reason.as_str()), (0, "Could not parse collector number `3★`")) ;
+
+    assert_eq!(import(&catalog, Format::Moxfield, "Count,Card\n1,Banish from Edoras\n").unwrap_err(), ImportError::MissingColumn("Name")) ;
+    assert_eq!(import(&catalog, Format::Moxfield, "Count,Name\n1,\"Banish\n").unwrap_err(), ImportError::Malformed { line: 2, reason: "Unclosed quoted field" }) ;
+}
+
+#[test]
+fn import_arena()
+{
+    let catalog = crate::ltr::collection() ;
+    let arena = "Deck\n4 Banish from Edoras (LTR) 1\n2 Boromir, Warden of the Tower *F*\n\nSideboard\n1 Bill the Poney (LTR) 3 *E*\n1 Bill the Poney (LTR) 42\n" ;
+
+    let (collection, report) = import(&catalog, Format::Arena, arena).unwrap() ;
+    assert_eq!(collection.count("LTR C 0001"), Some(4)) ;
+    assert_eq!(collection.count("LTR R 0004"), Some(2)) ;
+    assert_eq!(collection.count("LTR U 0003"), Some(1)) ;
+    assert_eq!(collection.count_variant("LTR R 0004", &Variant { finish: Finish::Foil, ..Variant::default() }), Some(2)) ;
+    assert_eq!(collection.count_variant("LTR U 0003", &Variant { finish: Finish::Etched, ..Variant::default() }), Some(1)) ;
+    assert_eq!(report.imported, 3) ;
+    assert_eq!(report.unmatched.len(), 1) ;
+    assert_eq!((report.unmatched[0].line, report.unmatched[0].number), (7, Some(42))) ;
+
+    // Only the deck's name is left out, not cards whose name starts with "Name"
+    let (_, report) = import(&catalog, Format::Arena, "About\nName Boromir's deck\n\nDeck\n1 Name the Nameless\n").unwrap() ;
+    assert_eq!(report.unmatched.iter().map(|row| row.name.as_deref()).collect::<Vec<_>>(), vec![Some("Name the Nameless")]) ;
+}
@@ -0,0 +1,415 @@
+use crate::cards::GenericCard;
+use crate::collections::{Collection, CollectionError};
+use crate::dates::Timestamp;
+use crate::ids::{AsId, Id};
+use crate::stock::{same_location, Stock, Variant};
+
+/// When, where from and why a change was made, such as `("import", "Moxfield export")` or `("manual", "sold to Sam")`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Origin
+{
+    pub time: Timestamp,
+    pub source: String,
+    pub reason: String,
+}
+
+impl Origin
+{
+    /// A change made now
+    pub fn now(source: &str, reason: &str) -> Origin
+    {
+        Origin::at(Timestamp::now(), source, reason)
+    }
+
+    pub fn at(time: Timestamp, source: &str, reason: &str) -> Origin
+    {
+        Origin { time, source: source.trim().to_string(), reason: reason.trim().to_string() }
+    }
+}
+
+/// Change of the number of copies of a variant at a location (`None` for unsorted copies)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LotChange
+{
+    pub variant: Variant,
+    pub location: Option<String>,
+    pub delta: isize,
+}
+
+/// A tag added to a card, or removed from it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagChange
+{
+    pub tag: String,
+    pub added: bool,
+}
+
+/// A recorded change of the copies and tags of a card
+#[derive(Debug, Clone)]
+pub struct Transaction<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub origin: Origin,
+    /// One change per variant and location, in the order their lots are found
+    pub changes: Vec<LotChange>,
+    pub tags: Vec<TagChange>,
+}
+
+impl Transaction<'_>
+{
+    /// Net change of the card's count, 0 for copies being moved
+    pub fn quantity(&self) -> isize
+    {
+        self.changes.iter().map(|change| change.delta).sum()
+    }
+}
+
+/// `2026-01-01T12:30:00Z -2 Banish from Edoras (LTR C 0001), manual: sold to Sam`
+impl std::fmt::Display for Transaction<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{} {:+} {}", self.origin.time, self.quantity(), self.card)? ;
+        write!(f, ", {}", self.origin.source)? ;
+        if !self.origin.reason.is_empty()
+        {
+            write!(f, ": {}", self.origin.reason)? ;
+        }
+        Ok(())
+    }
+}
+
+/// A collection whose every change is recorded as a [`Transaction`], so that changes can be undone and redone,
+/// and the collection looked at as it was at any point in time<br/>
+/// The log is kept with the collection by [`crate::persistence::save_journal`]
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::journal::{Journal, Origin};
+/// use mtg_multitool::ltr;
+///
+/// let new_year = Timestamp::from_date(2026, 1, 1).unwrap() ;
+/// let mut journal = Journal::new(Collection::from_counts(&ltr::collection(), [("LTR C 0001", 0)]).unwrap()) ;
+/// journal.add("LTR C 0001", 4, Origin::at(new_year, "manual", "opened boosters")).unwrap() ;
+/// journal.remove("LTR C 0001", 10, Origin::at(new_year.plus(60), "manual", "typo")).unwrap_err() ;
+/// journal.remove("LTR C 0001", 3, Origin::at(new_year.plus(3600), "manual", "typo")).unwrap() ;
+///
+/// assert_eq!(journal.undo().map(|transaction| transaction.quantity()), Some(-3)) ;
+/// assert_eq!(journal.collection().count("LTR C 0001"), Some(4)) ;
+/// assert_eq!(journal.redo().map(|transaction| transaction.quantity()), Some(-3)) ;
+/// assert_eq!(journal.at(new_year.plus(60)).count("LTR C 0001"), Some(4)) ;
+/// assert_eq!(journal.at(new_year).count("LTR C 0001"), Some(4)) ;
+/// assert_eq!(journal.at(Timestamp::from_date(2025, 12, 31).unwrap()).count("LTR C 0001"), Some(0)) ;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Journal<'gc>
+{
+    collection: Collection<'gc>,
+    /// Oldest first
+    transactions: Vec<Transaction<'gc>>,
+    /// Most recently undone last
+    undone: Vec<Transaction<'gc>>,
+}
+
+impl<'gc> Journal<'gc>
+{
+    /// Starts recording the changes of a collection, its current copies being there from the start
+    pub fn new(collection: Collection<'gc>) -> Journal<'gc>
+    {
+        Journal::with_transactions(collection, vec![])
+    }
+
+    /// A collection along with the transactions which led to it, oldest first
+    pub(crate) fn with_transactions(collection: Collection<'gc>, transactions: Vec<Transaction<'gc>>) -> Journal<'gc>
+    {
+        Journal { collection, transactions, undone: vec![] }
+    }
+
+    pub fn collection(&self) -> &Collection<'gc>
+    {
+        &self.collection
+    }
+
+    /// Stops recording changes
+    pub fn into_collection(self) -> Collection<'gc>
+    {
+        self.collection
+    }
+
+    /// Recorded transactions, oldest first (undone ones excluded)
+    pub fn transactions(&self) -> &[Transaction<'gc>]
+    {
+        &self.transactions
+    }
+
+    /// Undone transactions, in the order [`Journal::redo`] applies them back
+    pub fn undone(&self) -> impl Iterator<Item = &Transaction<'gc>>
+    {
+        self.undone.iter().rev()
+    }
+
+    /// Applies an operation to a card of the collection, and records how its copies and tags changed<br/>
+    /// Nothing is recorded if the operation fails or changes nothing. Recording a change forgets the undone transactions.
+    /// Sources may not contain `;` nor line breaks, and reasons may not contain line breaks (see [`CollectionError::ReservedCharacter`])
+    fn record<'any, T, R, F>(&mut self, id: T, origin: Origin, operation: F) -> Result<R, CollectionError<'gc>>
+        where T: AsId<'any>, F: FnOnce(&mut Collection<'gc>, Id<'any>) -> Result<R, CollectionError<'gc>>
+    {
+        crate::collections::check_label(&origin.source)? ;
+        if origin.reason.contains(['\n', '\r'])
+        {
+            return Err(CollectionError::ReservedCharacter(origin.reason)) ;
+        }
+        let id = id.as_id() ;
+        let card = self.collection.resolve(id, self.collection.policy())?.clone() ;
+        let before = self.collection.stock(id).cloned().unwrap_or_default() ;
+
+        let result = operation(&mut self.collection, id)? ;
+
+        let after = self.collection.stock(id).cloned().unwrap_or_default() ;
+        let (changes, tags) = (lot_changes(&before, &after), tag_changes(&before, &after)) ;
+        if !changes.is_empty() || !tags.is_empty()
+        {
+            self.transactions.push(Transaction { card, origin, changes, tags }) ;
+            self.undone.clear() ;
+        }
+        Ok(result)
+    }
+
+    /// Recorded [`Collection::add`]
+    pub fn add<'any, T>(&mut self, id: T, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.add(id, quantity))
+    }
+
+    /// Recorded [`Collection::add_variant`]
+    pub fn add_variant<'any, T>(&mut self, id: T, variant: Variant, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.add_variant(id, variant, quantity))
+    }
+
+    /// Recorded [`Collection::add_at`]
+    pub fn add_at<'any, T>(&mut self, id: T, variant: Variant, location: &str, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.add_at(id, variant, location, quantity))
+    }
+
+    /// Recorded [`Collection::remove`]
+    pub fn remove<'any, T>(&mut self, id: T, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.remove(id, quantity))
+    }
+
+    /// Recorded [`Collection::remove_variant`]
+    pub fn remove_variant<'any, T>(&mut self, id: T, variant: &Variant, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.remove_variant(id, variant, quantity))
+    }
+
+    /// Recorded [`Collection::remove_at`]
+    pub fn remove_at<'any, T>(&mut self, id: T, variant: &Variant, location: Option<&str>, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.remove_at(id, variant, location, quantity))
+    }
+
+    /// Recorded [`Collection::saturating_remove`]
+    pub fn saturating_remove<'any, T>(&mut self, id: T, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.saturating_remove(id, quantity))
+    }
+
+    /// Recorded [`Collection::move_copies`]
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::journal::{Journal, Origin};
+    /// use mtg_multitool::ltr;
+    ///
+    /// let mut journal = Journal::new(Collection::from_counts(&ltr::collection(), [("LTR C 0001", 4)]).unwrap()) ;
+    /// assert_eq!(journal.move_copies("LTR C 0001", None, Some("Binder 1"), 2, Origin::now("manual", "sorting")), Ok(2)) ;
+    ///
+    /// assert_eq!(journal.transactions()[0].quantity(), 0) ;
+    /// assert_eq!(journal.transactions()[0].changes.len(), 2) ;
+    /// ```
+    pub fn move_copies<'any, T>(&mut self, id: T, from: Option<&str>, to: Option<&str>, quantity: usize, origin: Origin) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.move_copies(id, from, to, quantity))
+    }
+
+    /// Recorded [`Collection::tag`]
+    pub fn tag<'any, T>(&mut self, id: T, tag: &str, origin: Origin) -> Result<bool, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.tag(id, tag))
+    }
+
+    /// Recorded [`Collection::untag`]
+    pub fn untag<'any, T>(&mut self, id: T, tag: &str, origin: Origin) -> Result<bool, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.record(id, origin, |collection, id| collection.untag(id, tag))
+    }
+
+    /// Reverts the most recent transaction, and returns it
+    pub fn undo(&mut self) -> Option<&Transaction<'gc>>
+    {
+        let transaction = self.transactions.pop()? ;
+        apply(&mut self.collection, &transaction, false) ;
+        self.undone.push(transaction) ;
+        self.undone.last()
+    }
+
+    /// Applies back the most recently undone transaction, and returns it
+    pub fn redo(&mut self) -> Option<&Transaction<'gc>>
+    {
+        let transaction = self.undone.pop()? ;
+        apply(&mut self.collection, &transaction, true) ;
+        self.transactions.push(transaction) ;
+        self.transactions.last()
+    }
+
+    /// The collection as it was at a point in time, every transaction made after it being reverted
+    pub fn at(&self, time: Timestamp) -> Collection<'gc>
+    {
+        let mut collection = self.collection.clone() ;
+        for transaction in self.transactions.iter().rev().filter(|transaction| transaction.origin.time > time)
+        {
+            apply(&mut collection, transaction, false) ;
+        }
+        collection
+    }
+}
+
+/// Changes of the lots of a stock, lots being compared by variant and location
+fn lot_changes(before: &Stock, after: &Stock) -> Vec<LotChange>
+{
+    let mut changes: Vec<LotChange> = vec![] ;
+    for lot in before.lots().chain(after.lots())
+    {
+        let location = lot.location.as_deref() ;
+        if changes.iter().any(|change| change.variant == lot.variant && same_location(change.location.as_deref(), location)) { continue ; }
+
+        let delta = after.count_at(&lot.variant, location) as isize - before.count_at(&lot.variant, location) as isize ;
+        if delta != 0
+        {
+            changes.push(LotChange { variant: lot.variant, location: lot.location.clone(), delta }) ;
+        }
+    }
+    changes
+}
+
+/// Tags added to a stock, then tags removed from it
+fn tag_changes(before: &Stock, after: &Stock) -> Vec<TagChange>
+{
+    let added = after.tags().filter(|tag| !before.has_tag(tag)).map(|tag| TagChange { tag: tag.to_string(), added: true }) ;
+    let removed = before.tags().filter(|tag| !after.has_tag(tag)).map(|tag| TagChange { tag: tag.to_string(), added: false }) ;
+    added.chain(removed).collect()
+}
+
+/// Applies the changes of a transaction to a collection, or reverts them<br/>
+/// Copies are only removed as long as there are some left, should the collection not match the transaction
+fn apply<'gc>(collection: &mut Collection<'gc>, transaction: &Transaction<'gc>, forward: bool)
+{
+    let card = transaction.card.id() ;
+    for change in &transaction.changes
+    {
+        let quantity = change.delta.unsigned_abs() ;
+        let location = change.location.as_deref() ;
+        let result = if (change.delta > 0) == forward
+        {
+            match location
+            {
+                Some(location) => collection.add_at(card, change.variant, location, quantity),
+                None => collection.add_variant(card, change.variant, quantity),
+            }
+        } else {
+            let owned = collection.stock(card).map_or(0, |stock| stock.count_at(&change.variant, location)) ;
+            collection.remove_at(card, &change.variant, location, quantity.min(owned))
+        } ;
+        debug_assert!(result.is_ok(), "The card of a transaction is in its collection") ;
+    }
+    for change in &transaction.tags
+    {
+        let result = match change.added == forward
+        {
+            true => collection.tag(card, &change.tag),
+            false => collection.untag(card, &change.tag),
+        } ;
+        debug_assert!(result.is_ok(), "The card of a transaction is in its collection") ;
+    }
+}
+
+#[test]
+fn journal_undo_redo()
+{
+    let catalog = crate::collections::test_catalog() ;
+    let mut journal = Journal::new(Collection::from_counts(&catalog, [("LTR C 0001", 2)]).unwrap()) ;
+    let foil = Variant { finish: crate::Finish::Foil, ..Variant::default() } ;
+    let day = |day| Timestamp::from_date(2026, 1, day).unwrap() ;
+
+    journal.add_at("LTR C 1", foil, "Binder 1", 2, Origin::at(day(2), "import", "Moxfield export")).unwrap() ;
+    journal.move_copies("LTR C 0001", Some("Binder 1"), None, 1, Origin::at(day(3), "manual", "sorting")).unwrap() ;
+    journal.add("LTR R 0004", 1, Origin::at(day(4), "manual", "opened a booster")).unwrap() ;
+    assert_eq!(journal.remove("LTR R 0004", 2, Origin::at(day(5), "manual", "")), Err(CollectionError::InsufficientQuantity { owned: 1, requested: 2 })) ;
+    assert_eq!(journal.add("Sauron", 1, Origin::at(day(5), "manual", "")), Err(CollectionError::UnknownCard)) ;
+    assert_eq!(journal.add("LTR R 0004", 0, Origin::at(day(5), "manual", "")), Ok(1)) ;
+    assert_eq!(journal.add("LTR R 0004", 1, Origin::at(day(5), "manual; import", "")), Err(CollectionError::ReservedCharacter("manual; import".to_string()))) ;
+    assert_eq!(journal.add("LTR R 0004", 1, Origin::at(day(5), "manual", "sold to Sam\nfor 2 EUR")), Err(CollectionError::ReservedCharacter("sold to Sam\nfor 2 EUR".to_string()))) ;
+    assert_eq!(journal.transactions().iter().map(Transaction::quantity).collect::<Vec<_>>(), vec![2, 0, 1]) ;
+    assert_eq!(journal.transactions()[1].changes, vec![
+        LotChange { variant: foil, location: Some("Binder 1".to_string()), delta: -1 },
+        LotChange { variant: foil, location: None, delta: 1 },
+    ]) ;
+    assert_eq!(journal.transactions()[0].to_string(), "2026-01-02T00:00:00Z +2 Banish from Edoras (LTR C 0001), import: Moxfield export") ;
+
+    // Undoing everything leads back to the starting collection
+    let current = journal.collection().clone() ;
+    while journal.undo().is_some() {}
+    assert_eq!(journal.collection().stock("LTR C 0001"), Some(&Stock::from(2))) ;
+    assert_eq!(journal.collection().count("LTR R 0004"), Some(0)) ;
+    assert_eq!(journal.undone().count(), 3) ;
+
+    while journal.redo().is_some() {}
+    assert_eq!(journal.collection().stock("LTR C 0001"), current.stock("LTR C 0001")) ;
+    assert!(current.diff(journal.collection()).is_empty()) ;
+
+    // Recording a change forgets the undone transactions
+    journal.undo() ;
+    journal.remove("LTR C 0001", 1, Origin::at(day(6), "manual", "traded")).unwrap() ;
+    assert!(journal.redo().is_none()) ;
+
+    let before_sorting = journal.at(day(2)) ;
+    assert_eq!(before_sorting.stock("LTR C 0001").map(|stock| stock.count_at(&foil, Some("binder 1"))), Some(2)) ;
+    assert_eq!(before_sorting.count("LTR C 0001"), Some(4)) ;
+    assert_eq!(journal.at(day(1)).count("LTR C 0001"), Some(2)) ;
+    assert_eq!(journal.at(day(6)).count("LTR C 0001"), Some(3)) ;
+
+    // Tags are recorded as well
+    assert_eq!(journal.tag("LTR C 0001", "trade", Origin::at(day(7), "manual", "")), Ok(true)) ;
+    assert_eq!(journal.tag("LTR C 0001", "Trade", Origin::at(day(7), "manual", "")), Ok(false)) ;
+    assert_eq!(journal.transactions().last().map(|transaction| transaction.tags.clone()), Some(vec![TagChange { tag: "trade".to_string(), added: true }])) ;
+    assert!(!journal.at(day(6)).stock("LTR C 0001").unwrap().has_tag("trade")) ;
+    journal.undo() ;
+    assert!(!journal.collection().stock("LTR C 0001").unwrap().has_tag("trade")) ;
+    journal.redo() ;
+    assert_eq!(journal.untag("LTR C 0001", "TRADE", Origin::at(day(8), "manual", "")), Ok(true)) ;
+    assert_eq!(journal.transactions().len(), 5) ;
+
+    // As well as removals of a lot, and removals stopping at 0
+    assert_eq!(journal.remove_at("LTR C 0001", &foil, Some("binder 1"), 2, Origin::at(day(9), "manual", "")), Err(CollectionError::InsufficientQuantity { owned: 1, requested: 2 })) ;
+    assert_eq!(journal.remove_at("LTR C 0001", &foil, Some("binder 1"), 1, Origin::at(day(9), "manual", "traded")), Ok(2)) ;
+    assert_eq!(journal.saturating_remove("LTR C 0001", 5, Origin::at(day(10), "manual", "sold")), Ok(0)) ;
+    assert_eq!(journal.saturating_remove("LTR C 0001", 5, Origin::at(day(11), "manual", "")), Ok(0)) ;
+    assert_eq!(journal.transactions().iter().skip(5).map(Transaction::quantity).collect::<Vec<_>>(), vec![-1, -2]) ;
+    assert_eq!(journal.at(day(9)).count("LTR C 0001"), Some(2)) ;
+    journal.undo() ;
+    journal.undo() ;
+    assert_eq!(journal.collection().stock("LTR C 0001").map(|stock| stock.count_at(&foil, Some("Binder 1"))), Some(1)) ;
+    assert_eq!(journal.collection().count("LTR C 0001"), Some(3)) ;
+}
@@ -1,14 +1,73 @@
-use crate::cards::GenericCard;
-
 /// Represents card unique identifiers (either name or `{series} {rarity} {internal series id}`)
 pub mod ids;
 
-/// Represents a generic card
+/// Represents cards: oracle cards, their printings, and generic cards joining both
 pub mod cards;
 
 /// Represents cards collections
 pub mod collections;
 
+/// Represents the owned copies of a card, broken down by finish, condition and language
+pub mod stock;
+
+/// Represents card names normalization, used whenever names are compared
+pub mod names;
+
+/// Represents decklist-style card references (`4x Boromir, Warden of the Tower [LTR]`)
+pub mod references;
+
+/// Represents the collection file format, to keep a collection between runs
+pub mod persistence;
+
+/// Represents the importers of other apps' inventory exports (Moxfield, Deckbox, Archidekt, Arena, Delver Lens)
+pub mod imports;
+
+/// Represents the exporters of collections to other apps and spreadsheets
+pub mod exports;
+
+/// Represents set completion statistics, per set and per rarity
+pub mod completion;
+
+/// Represents the transaction log of a collection, with undo, redo and point-in-time views
+pub mod journal;
+
+/// Represents want lists: the cards wanted, how many, how much and in which printing
+pub mod wants;
+
+/// Represents trades between two collections, matched against each other's want lists
+pub mod trades;
+
+/// Represents the cards lent to and borrowed from friends
+pub mod loans;
+
+/// Represents built decks, and the copies they reserve from a collection
+pub mod decks;
+
+/// Represents the copies owned beyond what is kept, to trade or sell them
+pub mod excess;
+
+/// Represents points in time, as written in collection files
+pub mod dates;
+
+/// Represents amounts of money, such as prices, and their currencies
+pub mod money;
+
+/// Represents exchange rates between currencies, as read from local files
+pub mod exchange;
+
+/// Represents local price snapshots, and the value they give to collections
+pub mod prices;
+
+/// Represents the prices of cards and the value of collections over time, as seen through dated snapshots
+pub mod history;
+
+/// Represents the csv reading and writing shared by the importers and exporters
+mod csv;
+
+/// Represents the (de)serialization of the core types, behind the `serde` feature
+#[cfg(feature = "serde")]
+mod serialization;
+
 /// Represents an MTG Card's rarity (Common / Uncommon / Rare / Mythical)
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum Rarity
@@ -19,6 +78,33 @@ pub enum Rarity
     Mythical,
 }
 
+impl Rarity
+{
+    /// Letter of the rarity, as found in serials (`'C'`, `'U'`, `'R'` or `'M'`)
+    pub fn code(&self) -> char
+    {
+        match self
+        {
+            Rarity::Common => 'C',
+            Rarity::Uncommon => 'U',
+            Rarity::Rare => 'R',
+            Rarity::Mythical => 'M',
+        }
+    }
+
+    /// English name of the rarity, such as `"Common"` or `"Mythic"`
+    pub fn name(&self) -> &'static str
+    {
+        match self
+        {
+            Rarity::Common => "Common",
+            Rarity::Uncommon => "Uncommon",
+            Rarity::Rare => "Rare",
+            Rarity::Mythical => "Mythic",
+        }
+    }
+}
+
 /// ```
 /// use mtg_multitool::Rarity;
 /// assert_eq!(Rarity::try_from("c"), Ok(Rarity::Common)) ;
@@ -53,6 +139,302 @@ fn rarity_try_from_str()
     assert_eq!(Rarity::try_from("m"), Ok(Rarity::Mythical)) ;
 }
 
+/// Represents the language a card is printed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Language
+{
+    #[default]
+    English,
+    French,
+    German,
+    Italian,
+    Spanish,
+    Portuguese,
+    Japanese,
+    Korean,
+    Russian,
+    SimplifiedChinese,
+    TraditionalChinese,
+    Phyrexian,
+}
+
+impl Language
+{
+    /// Short code of the language, such as `"en"` or `"fr"`
+    pub fn code(&self) -> &'static str
+    {
+        use Language::* ;
+        match self
+        {
+            English => "en",
+            French => "fr",
+            German => "de",
+            Italian => "it",
+            Spanish => "es",
+            Portuguese => "pt",
+            Japanese => "ja",
+            Korean => "ko",
+            Russian => "ru",
+            SimplifiedChinese => "zhs",
+            TraditionalChinese => "zht",
+            Phyrexian => "ph",
+        }
+    }
+
+    /// English name of the language, such as `"English"` or `"Simplified Chinese"`
+    pub fn name(&self) -> &'static str
+    {
+        use Language::* ;
+        match self
+        {
+            English => "English",
+            French => "French",
+            German => "German",
+            Italian => "Italian",
+            Spanish => "Spanish",
+            Portuguese => "Portuguese",
+            Japanese => "Japanese",
+            Korean => "Korean",
+            Russian => "Russian",
+            SimplifiedChinese => "Simplified Chinese",
+            TraditionalChinese => "Traditional Chinese",
+            Phyrexian => "Phyrexian",
+        }
+    }
+}
+
+/// Recognizes both short codes (`"fr"`) and english names (`"French"`)
+/// ```
+/// use mtg_multitool::Language;
+/// assert_eq!(Language::try_from("fr"), Ok(Language::French)) ;
+/// assert_eq!(Language::try_from("Japanese"), Ok(Language::Japanese)) ;
+/// ```
+impl<'a> TryFrom<&'a str> for Language
+{
+    type Error = &'static str;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        use Language::* ;
+        Ok(match value.trim().to_ascii_lowercase().as_str()
+        {
+            "en" | "english" => English,
+            "fr" | "french" => French,
+            "de" | "german" => German,
+            "it" | "italian" => Italian,
+            "es" | "spanish" => Spanish,
+            "pt" | "portuguese" => Portuguese,
+            "ja" | "jp" | "japanese" => Japanese,
+            "ko" | "kr" | "korean" => Korean,
+            "ru" | "russian" => Russian,
+            "zhs" | "cs" | "simplified chinese" | "chinese simplified" => SimplifiedChinese,
+            "zht" | "ct" | "traditional chinese" | "chinese traditional" => TraditionalChinese,
+            "ph" | "phyrexian" => Phyrexian,
+
+            &_ => return Err("Could not parse into language"),
+        })
+    }
+}
+
+#[test]
+fn language_try_from_str()
+{
+    use crate::Language::* ;
+    assert_eq!(Language::try_from(" EN "), Ok(English)) ;
+    assert_eq!(Language::try_from("german"), Ok(German)) ;
+    assert_eq!(Language::try_from("Chinese Simplified"), Ok(SimplifiedChinese)) ;
+    assert_eq!(Language::try_from("zht"), Ok(TraditionalChinese)) ;
+    assert_eq!(Language::try_from("Elvish"), Err("Could not parse into language")) ;
+
+    for language in [English, French, German, Italian, Spanish, Portuguese, Japanese, Korean, Russian, SimplifiedChinese, TraditionalChinese, Phyrexian]
+    {
+        assert_eq!(Language::try_from(language.code()), Ok(language)) ;
+    }
+}
+
+/// Represents the frame treatment of a printing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Frame
+{
+    #[default]
+    Standard,
+    Borderless,
+    Showcase,
+    ExtendedArt,
+    Retro,
+}
+
+impl Frame
+{
+    /// Short code of the frame, such as `"standard"` or `"borderless"`
+    pub fn code(&self) -> &'static str
+    {
+        match self
+        {
+            Frame::Standard => "standard",
+            Frame::Borderless => "borderless",
+            Frame::Showcase => "showcase",
+            Frame::ExtendedArt => "extended",
+            Frame::Retro => "retro",
+        }
+    }
+}
+
+/// ```
+/// use mtg_multitool::Frame;
+/// assert_eq!(Frame::try_from("Borderless"), Ok(Frame::Borderless)) ;
+/// assert_eq!(Frame::try_from("extended art"), Ok(Frame::ExtendedArt)) ;
+/// ```
+impl<'a> TryFrom<&'a str> for Frame
+{
+    type Error = &'static str;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Ok(match value.trim().to_ascii_lowercase().as_str()
+        {
+            "" | "standard" | "normal" => Frame::Standard,
+            "borderless" => Frame::Borderless,
+            "showcase" => Frame::Showcase,
+            "extended" | "extended art" | "extendedart" => Frame::ExtendedArt,
+            "retro" | "old" => Frame::Retro,
+
+            &_ => return Err("Could not parse into frame"),
+        })
+    }
+}
+
+/// Represents the finish of a copy: regular, foil or etched foil
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Finish
+{
+    #[default]
+    Nonfoil,
+    Foil,
+    Etched,
+}
+
+impl Finish
+{
+    /// Short code of the finish, such as `"nonfoil"` or `"foil"`
+    pub fn code(&self) -> &'static str
+    {
+        match self
+        {
+            Finish::Nonfoil => "nonfoil",
+            Finish::Foil => "foil",
+            Finish::Etched => "etched",
+        }
+    }
+}
+
+/// Recognizes codes, as well as the yes/no flags of foil columns (`"foil"`, `"true"` or `""`)
+/// ```
+/// use mtg_multitool::Finish;
+/// assert_eq!(Finish::try_from(""), Ok(Finish::Nonfoil)) ;
+/// assert_eq!(Finish::try_from("Foil"), Ok(Finish::Foil)) ;
+/// assert_eq!(Finish::try_from("etched foil"), Ok(Finish::Etched)) ;
+/// ```
+impl<'a> TryFrom<&'a str> for Finish
+{
+    type Error = &'static str;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Ok(match value.trim().to_ascii_lowercase().as_str()
+        {
+            "" | "nonfoil" | "non-foil" | "normal" | "no" | "false" => Finish::Nonfoil,
+            "foil" | "yes" | "true" => Finish::Foil,
+            "etched" | "etched foil" | "foil etched" => Finish::Etched,
+
+            &_ => return Err("Could not parse into finish"),
+        })
+    }
+}
+
+/// Represents the condition of a copy, from near mint to damaged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Condition
+{
+    #[default]
+    NearMint,
+    LightlyPlayed,
+    ModeratelyPlayed,
+    HeavilyPlayed,
+    Damaged,
+}
+
+impl Condition
+{
+    /// Short code of the condition, such as `"NM"` or `"DMG"`
+    pub fn code(&self) -> &'static str
+    {
+        match self
+        {
+            Condition::NearMint => "NM",
+            Condition::LightlyPlayed => "LP",
+            Condition::ModeratelyPlayed => "MP",
+            Condition::HeavilyPlayed => "HP",
+            Condition::Damaged => "DMG",
+        }
+    }
+
+    /// English name of the condition, such as `"Near Mint"`
+    pub fn name(&self) -> &'static str
+    {
+        match self
+        {
+            Condition::NearMint => "Near Mint",
+            Condition::LightlyPlayed => "Lightly Played",
+            Condition::ModeratelyPlayed => "Moderately Played",
+            Condition::HeavilyPlayed => "Heavily Played",
+            Condition::Damaged => "Damaged",
+        }
+    }
+}
+
+/// Recognizes codes and names, including the grading names of other apps (`"Good (Lightly Played)"`, `"Poor"`)
+/// ```
+/// use mtg_multitool::Condition;
+/// assert_eq!(Condition::try_from("nm"), Ok(Condition::NearMint)) ;
+/// assert_eq!(Condition::try_from("Good (Lightly Played)"), Ok(Condition::LightlyPlayed)) ;
+/// assert_eq!(Condition::try_from("GD"), Condition::try_from("Good (Lightly Played)")) ;
+/// assert_eq!(Condition::try_from("Heavily Played"), Ok(Condition::HeavilyPlayed)) ;
+/// ```
+impl<'a> TryFrom<&'a str> for Condition
+{
+    type Error = &'static str;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Ok(match value.trim().to_ascii_lowercase().as_str()
+        {
+            "" | "nm" | "near mint" | "m" | "mint" => Condition::NearMint,
+            "lp" | "lightly played" | "sp" | "slightly played" | "ex" | "excellent" | "gd" | "good" | "good (lightly played)" => Condition::LightlyPlayed,
+            "mp" | "moderately played" | "pl" | "played" => Condition::ModeratelyPlayed,
+            "hp" | "heavily played" => Condition::HeavilyPlayed,
+            "dmg" | "damaged" | "po" | "poor" => Condition::Damaged,
+
+            &_ => return Err("Could not parse into condition"),
+        })
+    }
+}
+
+#[test]
+fn finish_and_condition_try_from_str()
+{
+    for finish in [Finish::Nonfoil, Finish::Foil, Finish::Etched]
+    {
+        assert_eq!(Finish::try_from(finish.code()), Ok(finish)) ;
+    }
+    assert_eq!(Finish::try_from("sparkly"), Err("Could not parse into finish")) ;
+
+    for condition in [Condition::NearMint, Condition::LightlyPlayed, Condition::ModeratelyPlayed, Condition::HeavilyPlayed, Condition::Damaged]
+    {
+        assert_eq!(Condition::try_from(condition.code()), Ok(condition)) ;
+        assert_eq!(Condition::try_from(condition.name()), Ok(condition)) ;
+    }
+    assert_eq!(Condition::try_from("Played"), Ok(Condition::ModeratelyPlayed)) ;
+    assert_eq!(Condition::try_from("Poor"), Ok(Condition::Damaged)) ;
+    assert_eq!(Condition::try_from("pristine"), Err("Could not parse into condition")) ;
+}
+
 
 /// Represents a card's kind, such as `"Legendary Land"` or `"Sorcery"`
 #[derive(Debug, Clone)]
@@ -101,8 +483,7 @@ impl<'mostly_static> TryFrom<&'mostly_static str> for Classification
     fn try_from(s: &'mostly_static str) -> Result<Self, Self::Error>
     {
         use Classification::{*} ;
-        let s = s.trim().to_ascii_lowercase() ;
-        let s = match s.as_str()
+        match crate::names::normalize(s).as_str()
         {
             "sorcery" => return Ok(Sorcery),
             "ritual" => return Ok(Ritual),
@@ -110,23 +491,24 @@ impl<'mostly_static> TryFrom<&'mostly_static str> for Classification
             "land" | "terrain" | "basic land" => return Ok(Terrain),
             "artifact" => return Ok(Artifact),
 
-            other => other,
+            _ => (),
         } ;
 
-
-        let split = s.split_once(" ") ;
+        // Prefixes are compared once normalized, but creature types keep the case they are written with
+        let s = s.trim() ;
+        let split = s.split_once(char::is_whitespace) ;
         if let Some((prefix, suffix)) = split
         {
-            return match prefix.trim().to_ascii_lowercase().as_str()
+            return match crate::names::normalize(prefix).as_str()
             {
                 "token" => Ok(Token(Box::new(Classification::try_from(suffix)?))),
                 "legendary" => Ok(Legendary(Box::new(Classification::try_from(suffix)?))),
 
                 "creature" => {
-                    let new_split = s.split_once("-");
+                    let new_split = s.split_once(['-', '\u{2013}', '\u{2014}']);
                     if let Some((label, class)) = new_split
                     {
-                        return if label.trim() == "creature"
+                        return if crate::names::normalize(label) == "creature"
                         {
                             Ok(Creature(class.trim().to_string()))
                         } else {
@@ -155,6 +537,8 @@ fn classification_try_from_str_base()
     assert_eq!(Classification::try_from("Legendary land"), Ok(Legendary(Box::new(Terrain)))) ;
 
     assert_eq!(Classification::try_from("Creature - Soldier"), Ok(Creature("Soldier".to_string()))) ;
+    assert_eq!(Classification::try_from("Legendary Creature — Human Soldier"), Ok(Legendary(Box::new(Creature("Human Soldier".to_string()))))) ;
+    assert!(matches!(Classification::try_from("legendary creature - Human Soldier"), Ok(Legendary(creature)) if matches!(creature.as_ref(), Creature(class) if class == "Human Soldier"))) ;
 
 }
 
@@ -180,7 +564,42 @@ fn classification_try_from_str_whitespaces()
     assert_eq!(Classification::try_from("Creature  -   Soldier"), Ok(Creature("  soldier ".to_string()))) ;
 }
 
-/// Ignores trailing whitespaces and case
+/// Formats the kind the way [`Classification::try_from`] reads it back
+/// ```
+/// use mtg_multitool::Classification::* ;
+/// assert_eq!(Legendary(Box::new(Creature("Human Soldier".to_string()))).to_string(), "Legendary Creature - Human Soldier") ;
+/// assert_eq!(Terrain.to_string(), "Land") ;
+/// ```
+impl std::fmt::Display for Classification
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        use Classification::* ;
+        match self
+        {
+            Sorcery => write!(f, "Sorcery"),
+            Ritual => write!(f, "Ritual"),
+            Enchantment => write!(f, "Enchantment"),
+            Terrain => write!(f, "Land"),
+            Artifact => write!(f, "Artifact"),
+            Creature(class) => write!(f, "Creature - {}", class.trim()),
+            Token(inner) => write!(f, "Token {}", inner),
+            Legendary(inner) => write!(f, "Legendary {}", inner),
+        }
+    }
+}
+
+#[test]
+fn classification_display()
+{
+    use Classification::{*} ;
+    for kind in [Sorcery, Ritual, Enchantment, Terrain, Artifact, Creature("Soldier".to_string()), Token(Box::new(Creature("Orc Army".to_string()))), Legendary(Box::new(Terrain))]
+    {
+        assert_eq!(Classification::try_from(kind.to_string().as_str()), Ok(kind)) ;
+    }
+}
+
+/// Ignores trailing whitespaces, case and diacritics (see [`names::normalize`])
 /// ```
 /// use mtg_multitool::Classification::* ;
 /// assert_eq!(Sorcery, Sorcery) ;
@@ -207,7 +626,7 @@ impl PartialEq for Classification {
             (Terrain, Terrain) |
             (Artifact, Artifact) => true,
 
-            (Creature(self_str), Creature(other_str)) => self_str.trim().to_ascii_uppercase() == other_str.trim().to_ascii_uppercase(),
+            (Creature(self_str), Creature(other_str)) => crate::names::same_name(self_str, other_str),
 
             (Legendary(self_inner), Legendary(other_inner)) |
             (Token(self_inner), Token(other_inner)) => self_inner == other_inner,
@@ -231,6 +650,7 @@ fn classification_partialeq()
 
     assert_eq!(Creature("Soldier".to_string()), Creature("Soldier".to_string())) ;
     assert_eq!(Creature("  soldieR ".to_string()), Creature("Soldier".to_string())) ;
+    assert_eq!(Creature("Elf  Noble".to_string()), Creature("ÉLF NOBLE".to_string())) ;
     assert_eq!(Legendary(Box::new(Creature("Sauron".to_string()))), Legendary(Box::new(Creature("Sauron".to_string())))) ;
     assert_eq!(Legendary(Box::new(Creature("SaURon ".to_string()))), Legendary(Box::new(Creature(" SaurON".to_string())))) ;
     assert_ne!(Legendary(Box::new(Creature("Sauron".to_string()))), Legendary(Box::new(Creature("Sauron, the Necromancer".to_string())))) ;
@@ -276,9 +696,44 @@ impl ManaCost
     { self.4 }
     pub fn green(&self) -> u8
     { self.5 }
+
+    /// Total cost, regardless of colors (also known as mana value)
+    pub fn value(&self) -> u32
+    {
+        [self.0, self.1, self.2, self.3, self.4, self.5].iter().map(|&mana| mana as u32).sum()
+    }
+}
+
+/// Formats the cost with mana symbols, such as `{2}{W}{W}`
+/// ```
+/// use mtg_multitool::ManaCost;
+/// assert_eq!(ManaCost::try_from("2 w w").unwrap().to_string(), "{2}{W}{W}") ;
+/// assert_eq!(ManaCost::try_from("0 blu bla").unwrap().to_string(), "{U}{B}") ;
+/// assert_eq!(ManaCost::zero().to_string(), "{0}") ;
+/// ```
+impl std::fmt::Display for ManaCost
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        if self.0 > 0 || self.value() == 0
+        {
+            write!(f, "{{{}}}", self.0)? ;
+        }
+
+        for (amount, symbol) in [(self.1, "{W}"), (self.2, "{U}"), (self.3, "{B}"), (self.4, "{R}"), (self.5, "{G}")]
+        {
+            for _ in 0..amount
+            {
+                f.write_str(symbol)? ;
+            }
+        }
+
+        Ok(())
+    }
 }
 
-/// Converts str `n {"w" | "blu" | "bla" | "r" | "g"}` with n >= 0 to a ManaCost data struct
+/// Converts str `n {"w" | "blu" | "bla" | "r" | "g"}` with n >= 0 to a ManaCost data struct<br/>
+/// Mana symbols, such as `{2}{W}{W}`, are also recognized
 impl TryFrom<&'_ str> for ManaCost
 {
     type Error = &'static str;
@@ -286,6 +741,7 @@ impl TryFrom<&'_ str> for ManaCost
     /// Converts str `n {"w" | "blu" | "bla" | "r" | "g"}` with n >= 0 to a ManaCost data struct
     fn try_from(value: &'_ str) -> Result<Self, Self::Error> {
         if value.trim() == "" { return Err("&str is empty") ; }
+        if value.trim().starts_with('{') { return ManaCost::from_symbols(value.trim()) ; }
 
         let mut result = ManaCost::zero() ;
 
@@ -325,15 +781,71 @@ impl TryFrom<&'_ str> for ManaCost
     }
 }
 
+impl ManaCost
+{
+    /// Converts mana symbols, such as `{2}{W}{W}`, to a ManaCost data struct
+    fn from_symbols(value: &str) -> Result<ManaCost, &'static str>
+    {
+        let mut result = ManaCost::zero() ;
+        let mut rest = value ;
+
+        while !rest.is_empty()
+        {
+            let (symbol, after) = rest.strip_prefix('{')
+                .and_then(|r| r.split_once('}'))
+                .ok_or("Mana symbols must be enclosed in braces")? ;
+
+            let slot = match symbol.trim().to_ascii_uppercase().as_str()
+            {
+                "W" => &mut result.1,
+                "U" => &mut result.2,
+                "B" => &mut result.3,
+                "R" => &mut result.4,
+                "G" => &mut result.5,
+                "C" => &mut result.0,
+                generic => {
+                    let amount = generic.parse::<u8>().map_err(|_| "Unrecognized mana symbol")? ;
+                    result.0 = result.0.checked_add(amount).ok_or("Mana cost is too high")? ;
+                    rest = after.trim_start() ;
+                    continue ;
+                },
+            } ;
+            *slot = slot.checked_add(1).ok_or("Mana cost is too high")? ;
+            rest = after.trim_start() ;
+        }
+
+        Ok(result)
+    }
+}
+
+#[test]
+fn manacost_symbols()
+{
+    let mana = ManaCost::try_from("{2}{W}{u}{B}{R}{G}{G}").unwrap() ;
+    assert_eq!((mana.colorless(), mana.white(), mana.blue(), mana.black(), mana.red(), mana.green()), (2, 1, 1, 1, 1, 2)) ;
+    assert_eq!(mana.value(), 8) ;
+    assert_eq!(mana.to_string(), "{2}{W}{U}{B}{R}{G}{G}") ;
+
+    let mana = ManaCost::try_from("{C}{C}").unwrap() ;
+    assert_eq!(mana.colorless(), 2) ;
+    assert_eq!(ManaCost::try_from("{0}").unwrap().value(), 0) ;
+
+    assert!(ManaCost::try_from("{2}{X}").is_err()) ;
+    assert!(ManaCost::try_from("{2").is_err()) ;
+    assert!(ManaCost::try_from("{2}W").is_err()) ;
+}
+
 #[path="../assets/ltr/mod.rs"]
 pub mod ltr ;
 
 #[test]
 fn ltr_collection()
 {
+    use crate::cards::GenericCard;
+
     let mut lua = ltr::collection() ;
 
-    lua.add("BAnish from Edoras", 1) ;
+    lua.add("BAnish from Edoras", 1).unwrap() ;
 
     println!("{:?}", lua) ;
     assert_eq!(
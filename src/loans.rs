@@ -0,0 +1,268 @@
+use crate::cards::GenericCard;
+use crate::collections::{Collection, CollectionError};
+use crate::dates::Timestamp;
+use crate::decks::Decks;
+use crate::ids::AsId;
+use crate::names::same_name;
+
+/// Which way copies went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction
+{
+    /// Copies of the collection, at a friend's
+    Lent,
+    /// Copies of a friend, not part of the collection
+    Borrowed,
+}
+
+/// Copies of a card lent to or borrowed from someone
+#[derive(Debug, Clone)]
+pub struct Loan<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub person: String,
+    pub count: usize,
+    pub direction: Direction,
+    pub since: Timestamp,
+    /// When the copies came back, `None` while the loan is outstanding
+    pub returned: Option<Timestamp>,
+}
+
+impl Loan<'_>
+{
+    pub fn is_outstanding(&self) -> bool
+    {
+        self.returned.is_none()
+    }
+}
+
+/// `2 Banish from Edoras (LTR C 0001) lent to Sam on 2026-01-01`
+impl std::fmt::Display for Loan<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{} {}", self.count, self.card)? ;
+        match self.direction
+        {
+            Direction::Lent => write!(f, " lent to {}", self.person)?,
+            Direction::Borrowed => write!(f, " borrowed from {}", self.person)?,
+        }
+        write!(f, " on {}", self.since.date_string())? ;
+        if let Some(returned) = self.returned
+        {
+            write!(f, ", returned on {}", returned.date_string())? ;
+        }
+        Ok(())
+    }
+}
+
+/// Cards lent to and borrowed from friends, returned loans included<br/>
+/// Lent copies still count as owned by the collection, but are not available (see [`Decks::available`]).
+/// People are compared once their names are normalized (see [`crate::names::normalize`])
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::decks::Decks;
+/// use mtg_multitool::loans::LoanLedger;
+/// use mtg_multitool::ltr;
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4)]).unwrap() ;
+/// let event = Timestamp::from_date(2026, 1, 10).unwrap() ;
+/// let mut loans = LoanLedger::default() ;
+/// loans.lend(&collection, &Decks::default(), "Banish from Edoras", "Sam", 3, event).unwrap() ;
+/// assert!(loans.lend(&collection, &Decks::default(), "Banish from Edoras", "Frodo", 2, event).is_err()) ;
+///
+/// assert_eq!(collection.count("Banish from Edoras"), Some(4)) ;
+/// assert_eq!(loans.available(&collection, "Banish from Edoras"), Ok(1)) ;
+///
+/// loans.take_back(&collection, "Banish from Edoras", "sam", 2, event.plus(86_400)).unwrap() ;
+/// assert_eq!(loans.available(&collection, "Banish from Edoras"), Ok(3)) ;
+/// assert_eq!(loans.outstanding().map(|loan| loan.to_string()).collect::<Vec<_>>(), vec!["1 Banish from Edoras (LTR C 0001) lent to Sam on 2026-01-10"]) ;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LoanLedger<'gc>
+{
+    /// In the order they were made, returned parts of a loan following it
+    loans: Vec<Loan<'gc>>,
+}
+
+impl<'gc> LoanLedger<'gc>
+{
+    /// Lends copies of a card of a collection to someone<br/>
+    /// Fails if less than `count` copies are neither reserved by the decks nor already lent (see [`Decks::available`])
+    pub fn lend<'any, T>(&mut self, collection: &Collection<'gc>, decks: &Decks<'gc>, id: T, person: &str, count: usize, since: Timestamp) -> Result<&Loan<'gc>, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let card = collection.resolve(id.as_id(), collection.policy())? ;
+        let available = decks.available(collection, self, id)? ;
+        if available < count
+        {
+            return Err(CollectionError::InsufficientQuantity { owned: available, requested: count }) ;
+        }
+        Ok(self.push(card.clone(), person, count, Direction::Lent, since))
+    }
+
+    /// Borrows copies of a card of a catalog (such as `ltr::collection()`) from someone
+    pub fn borrow<'any, T>(&mut self, catalog: &Collection<'gc>, id: T, person: &str, count: usize, since: Timestamp) -> Result<&Loan<'gc>, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let card = catalog.resolve(id, catalog.policy())? ;
+        Ok(self.push(card.clone(), person, count, Direction::Borrowed, since))
+    }
+
+    fn push(&mut self, card: GenericCard<'gc>, person: &str, count: usize, direction: Direction, since: Timestamp) -> &Loan<'gc>
+    {
+        self.loans.push(Loan { card, person: person.trim().to_string(), count, direction, since, returned: None }) ;
+        self.loans.last().expect("A loan was just pushed")
+    }
+
+    /// Marks copies lent to someone as returned, oldest loans first; returns the number of copies still lent to them
+    pub fn take_back<'any, T>(&mut self, collection: &Collection<'gc>, id: T, person: &str, count: usize, on: Timestamp) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.mark_returned(collection, id, person, Direction::Lent, count, on)
+    }
+
+    /// Marks copies borrowed from someone as returned, oldest loans first; returns the number of copies still borrowed from them
+    pub fn give_back<'any, T>(&mut self, catalog: &Collection<'gc>, id: T, person: &str, count: usize, on: Timestamp) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        self.mark_returned(catalog, id, person, Direction::Borrowed, count, on)
+    }
+
+    /// Splits the returned copies off the outstanding loans, oldest first<br/>
+    /// Nothing is returned if less than `count` copies are outstanding
+    fn mark_returned<'any, T>(&mut self, collection: &Collection<'gc>, id: T, person: &str, direction: Direction, count: usize, on: Timestamp) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let card = collection.resolve(id, collection.policy())? ;
+        let matching = |loan: &Loan<'_>| loan.is_outstanding() && loan.direction == direction && same_name(&loan.person, person) && loan.card.same_printing(card) ;
+
+        let outstanding: usize = self.loans.iter().filter(|loan| matching(loan)).map(|loan| loan.count).sum() ;
+        if outstanding < count
+        {
+            return Err(CollectionError::InsufficientQuantity { owned: outstanding, requested: count }) ;
+        }
+
+        let mut left = count ;
+        let mut index = 0 ;
+        while left > 0 && index < self.loans.len()
+        {
+            if matching(&self.loans[index])
+            {
+                let loan = &mut self.loans[index] ;
+                let returned = left.min(loan.count) ;
+                left -= returned ;
+                if returned == loan.count
+                {
+                    loan.returned = Some(on) ;
+                } else {
+                    loan.count -= returned ;
+                    let part = Loan { count: returned, returned: Some(on), ..loan.clone() } ;
+                    self.loans.insert(index + 1, part) ;
+                    index += 1 ;
+                }
+            }
+            index += 1 ;
+        }
+
+        Ok(outstanding - count)
+    }
+
+    /// Every loan, returned ones included, in the order they were made
+    pub fn loans(&self) -> impl Iterator<Item = &Loan<'gc>>
+    {
+        self.loans.iter()
+    }
+
+    /// Loans whose copies have not come back yet
+    pub fn outstanding(&self) -> impl Iterator<Item = &Loan<'gc>>
+    {
+        self.loans.iter().filter(|loan| loan.is_outstanding())
+    }
+
+    /// Outstanding loans grouped by person, in the order people were first lent to or borrowed from
+    pub fn by_person(&self) -> Vec<(&str, Vec<&Loan<'gc>>)>
+    {
+        let mut people: Vec<(&str, Vec<&Loan<'gc>>)> = vec![] ;
+        for loan in self.outstanding()
+        {
+            match people.iter_mut().find(|(person, _)| same_name(person, &loan.person))
+            {
+                Some((_, loans)) => loans.push(loan),
+                None => people.push((&loan.person, vec![loan])),
+            }
+        }
+        people
+    }
+
+    /// Number of copies of a card currently lent, or borrowed
+    pub fn outstanding_count(&self, card: &GenericCard<'_>, direction: Direction) -> usize
+    {
+        self.outstanding()
+            .filter(|loan| loan.direction == direction && loan.card.same_printing(card))
+            .map(|loan| loan.count)
+            .sum()
+    }
+
+    /// Number of owned copies of a card which are not lent, whether decks reserve them or not
+    pub fn available<'any, T>(&self, collection: &Collection<'gc>, id: T) -> Result<usize, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let card = collection.resolve(id, collection.policy())? ;
+        let owned = collection.stock(card.id()).map_or(0, |stock| stock.total()) ;
+        Ok(owned.saturating_sub(self.outstanding_count(card, Direction::Lent)))
+    }
+}
+
+#[test]
+fn loan_ledger()
+{
+    let catalog = crate::collections::test_catalog() ;
+    let collection = Collection::from_counts(&catalog, [("LTR C 0001", 4), ("LTC R 0001", 1)]).unwrap() ;
+    let day = |day| Timestamp::from_date(2026, 1, day).unwrap() ;
+
+    let mut loans = LoanLedger::default() ;
+    loans.lend(&collection, &Decks::default(), "LTR C 0001", "Sam", 2, day(1)).unwrap() ;
+    loans.lend(&collection, &Decks::default(), "LTR C 0001", " Frodo ", 1, day(2)).unwrap() ;
+    loans.lend(&collection, &Decks::default(), "LTR C 0001", "SAM", 1, day(3)).unwrap() ;
+    assert_eq!(loans.lend(&collection, &Decks::default(), "LTR C 0001", "Sam", 1, day(3)).unwrap_err(), CollectionError::InsufficientQuantity { owned: 0, requested: 1 }) ;
+    assert!(matches!(loans.lend(&collection, &Decks::default(), "Banish from Edoras", "Sam", 1, day(3)), Err(CollectionError::AmbiguousId(_)))) ;
+    loans.borrow(&catalog, "LTR R 0004", "Frodo", 1, day(4)).unwrap() ;
+
+    // Lent copies of another printing are not counted
+    assert_eq!(loans.available(&collection, "LTC R 0001"), Ok(1)) ;
+    assert_eq!(loans.available(&collection, "LTR C 0001"), Ok(0)) ;
+    assert_eq!(loans.available(&collection, "LTR R 0004"), Ok(0)) ;
+    assert_eq!(collection.count("LTR C 0001"), Some(4)) ;
+
+    let people = loans.by_person() ;
+    assert_eq!(people.iter().map(|(person, loans)| (*person, loans.iter().map(|loan| loan.count).collect::<Vec<_>>())).collect::<Vec<_>>(), vec![
+        ("Sam", vec![2, 1]),
+        ("Frodo", vec![1, 1]),
+    ]) ;
+
+    // Returns split the oldest loans first
+    assert_eq!(loans.take_back(&collection, "LTR C 0001", "sam", 4, day(5)), Err(CollectionError::InsufficientQuantity { owned: 3, requested: 4 })) ;
+    assert_eq!(loans.take_back(&collection, "LTR C 0001", "sam", 1, day(5)), Ok(2)) ;
+    assert_eq!(loans.take_back(&collection, "LTR C 0001", "sam", 2, day(6)), Ok(0)) ;
+    assert_eq!(loans.loans().map(|loan| (loan.person.as_str(), loan.count, loan.returned.map(|on| on.date().2))).collect::<Vec<_>>(), vec![
+        ("Sam", 1, Some(6)),
+        ("Sam", 1, Some(5)),
+        ("Frodo", 1, None),
+        ("SAM", 1, Some(6)),
+        ("Frodo", 1, None),
+    ]) ;
+    assert_eq!(loans.available(&collection, "LTR C 0001"), Ok(3)) ;
+
+    assert_eq!(loans.give_back(&catalog, "LTR R 0004", "frodo", 1, day(7)), Ok(0)) ;
+    assert_eq!(loans.outstanding_count(catalog.resolve("LTR R 0004", catalog.policy()).unwrap(), Direction::Borrowed), 0) ;
+    assert_eq!(
+        loans.outstanding().map(|loan| loan.to_string()).collect::<Vec<_>>(),
+        vec!["1 Banish from Edoras (LTR C 0001) lent to Frodo on 2026-01-02"]
+    ) ;
+    assert_eq!(
+        loans.loans().last().map(|loan| loan.to_string()),
+        Some("1 Boromir, Warden of the Tower (LTR R 0004) borrowed from Frodo on 2026-01-04, returned on 2026-01-07".to_string())
+    ) ;
+}
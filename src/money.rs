@@ -0,0 +1,254 @@
+/// Number of decimals kept by amounts
+const DECIMALS: u32 = 6 ;
+/// An amount of 1, in millionths
+const UNIT: i128 = 1_000_000 ;
+
+/// A decimal amount of money, kept in millionths to stay exact<br/>
+/// Written with at least 2 decimals, such as `12.50` or `0.0125`, and read from `12`, `12.5`, `12.50` or `12,50`
+/// ```
+/// use mtg_multitool::money::Amount;
+///
+/// let price = Amount::try_from("12,5").unwrap() ;
+/// assert_eq!(price, Amount::from_cents(1250)) ;
+/// assert_eq!((price * 3 - Amount::from_cents(50)).to_string(), "37.00") ;
+/// assert_eq!([price, price].into_iter().sum::<Amount>().to_string(), "25.00") ;
+/// assert_eq!(Amount::try_from("0.0125").unwrap().percent(50).to_string(), "0.00625") ;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Amount(i128) ;
+
+impl Amount
+{
+    pub fn from_cents(cents: i64) -> Amount
+    {
+        Amount(i128::from(cents) * (UNIT / 100))
+    }
+
+    /// The amount in millionths
+    pub(crate) fn from_micros(micros: i128) -> Amount
+    {
+        Amount(micros)
+    }
+
+    pub(crate) fn micros(&self) -> i128
+    {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool
+    {
+        self.0 == 0
+    }
+
+    pub fn abs(&self) -> Amount
+    {
+        Amount(self.0.saturating_abs())
+    }
+
+    /// The amount rounded to a number of decimals, halves away from 0 (`round(2)` rounds to the cent)
+    pub fn round(&self, decimals: u32) -> Amount
+    {
+        let step = 10_i128.pow(DECIMALS - decimals.min(DECIMALS)) ;
+        Amount(divide(self.0, step).saturating_mul(step))
+    }
+
+    /// A percentage of the amount (`percent(50)` halves it)
+    pub fn percent(&self, percent: u32) -> Amount
+    {
+        Amount(divide(self.0.saturating_mul(i128::from(percent)), 100))
+    }
+}
+
+/// `value / divisor`, halves rounded away from 0
+pub(crate) fn divide(value: i128, divisor: i128) -> i128
+{
+    let half = divisor / 2 ;
+    value.saturating_add(if value < 0 { -half } else { half }) / divisor
+}
+
+/// Reads a decimal number such as `-12`, `12.5`, `,5` or `12,50`, scaled by `10^decimals`<br/>
+/// Fails if it has more decimals
+pub(crate) fn parse_decimal(value: &str, decimals: u32) -> Result<i128, &'static str>
+{
+    let value = value.trim() ;
+    let (negative, digits) = match value.strip_prefix('-')
+    {
+        Some(digits) => (true, digits.trim_start()),
+        None => (false, value),
+    } ;
+
+    let (units, fraction) = digits.split_once(['.', ',']).unwrap_or((digits, "")) ;
+    if units.is_empty() && fraction.is_empty() { return Err("No number given") ; }
+    if !units.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) { return Err("Could not parse number") ; }
+    if fraction.len() > decimals as usize { return Err("Too many decimals") ; }
+
+    let units: i128 = if units.is_empty() { 0 } else { units.parse().map_err(|_| "Number too large")? } ;
+    let fraction: i128 = if fraction.is_empty() { 0 } else { format!("{:0<width$}", fraction, width = decimals as usize).parse().map_err(|_| "Number too large")? } ;
+    let scaled = units.checked_mul(10_i128.pow(decimals)).and_then(|scaled| scaled.checked_add(fraction)).ok_or("Number too large")? ;
+    Ok(if negative { -scaled } else { scaled })
+}
+
+impl std::ops::Add for Amount
+{
+    type Output = Amount ;
+
+    fn add(self, rhs: Amount) -> Amount
+    {
+        Amount(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl std::ops::Sub for Amount
+{
+    type Output = Amount ;
+
+    fn sub(self, rhs: Amount) -> Amount
+    {
+        Amount(self.0.saturating_sub(rhs.0))
+    }
+}
+
+/// Price of several copies
+impl std::ops::Mul<usize> for Amount
+{
+    type Output = Amount ;
+
+    fn mul(self, rhs: usize) -> Amount
+    {
+        Amount(self.0.saturating_mul(i128::try_from(rhs).unwrap_or(i128::MAX)))
+    }
+}
+
+impl std::iter::Sum for Amount
+{
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount
+    {
+        iter.fold(Amount::default(), |total, amount| total + amount)
+    }
+}
+
+impl std::fmt::Display for Amount
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let sign = if self.0 < 0 { "-" } else { "" } ;
+        let micros = self.0.unsigned_abs() ;
+        let decimals = format!("{:06}", micros % UNIT.unsigned_abs()) ;
+        let decimals = decimals.trim_end_matches('0') ;
+        write!(f, "{}{}.{:0<2}", sign, micros / UNIT.unsigned_abs(), decimals)
+    }
+}
+
+impl TryFrom<&str> for Amount
+{
+    type Error = &'static str ;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error>
+    {
+        parse_decimal(value, DECIMALS).map(Amount)
+    }
+}
+
+/// An amount in a currency<br/>
+/// Written rounded to the cent, such as `12.50 EUR`
+/// ```
+/// use mtg_multitool::money::{Amount, Currency, Money};
+///
+/// let price = Money::new(Amount::try_from("0.125").unwrap(), Currency::try_from("EUR").unwrap()) ;
+/// assert_eq!(price.to_string(), "0.13 EUR") ;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money
+{
+    pub amount: Amount,
+    pub currency: Currency,
+}
+
+impl Money
+{
+    pub fn new(amount: Amount, currency: Currency) -> Money
+    {
+        Money { amount, currency }
+    }
+}
+
+/// Price of several copies
+impl std::ops::Mul<usize> for Money
+{
+    type Output = Money ;
+
+    fn mul(self, rhs: usize) -> Money
+    {
+        Money { amount: self.amount * rhs, ..self }
+    }
+}
+
+impl std::fmt::Display for Money
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{} {}", self.amount.round(2), self.currency)
+    }
+}
+
+/// A currency, by its ISO 4217 code such as `EUR` or `USD`
+/// ```
+/// use mtg_multitool::money::Currency;
+///
+/// let euro = Currency::try_from(" eur").unwrap() ;
+/// assert_eq!(euro.to_string(), "EUR") ;
+/// assert!(Currency::try_from("euro").is_err()) ;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Currency([u8 ; 3]) ;
+
+impl Currency
+{
+    pub fn code(&self) -> &str
+    {
+        std::str::from_utf8(&self.0).expect("Currency codes are ASCII letters")
+    }
+}
+
+impl std::fmt::Display for Currency
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl TryFrom<&str> for Currency
+{
+    type Error = &'static str ;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error>
+    {
+        match value.trim().to_ascii_uppercase().as_bytes()
+        {
+            &[a, b, c] if [a, b, c].iter().all(u8::is_ascii_uppercase) => Ok(Currency([a, b, c])),
+            _ => Err("Expected a 3-letter currency code"),
+        }
+    }
+}
+
+#[test]
+fn amount_try_from_str()
+{
+    assert_eq!(Amount::try_from("12"), Ok(Amount::from_cents(1200))) ;
+    assert_eq!(Amount::try_from(" 0.05 "), Ok(Amount::from_cents(5))) ;
+    assert_eq!(Amount::try_from(".5"), Ok(Amount::from_cents(50))) ;
+    assert_eq!(Amount::try_from("-3,2"), Ok(Amount::from_cents(-320))) ;
+    assert_eq!(Amount::try_from("1.234"), Ok(Amount(1_234_000))) ;
+    assert_eq!(Amount::from_cents(-320).to_string(), "-3.20") ;
+    assert_eq!(Amount::from_cents(-5).to_string(), "-0.05") ;
+    assert_eq!(Amount(1_234_500).to_string(), "1.2345") ;
+    assert_eq!((Amount::from_cents(1250).percent(60), Amount::from_cents(5).percent(50), Amount::from_cents(-5).percent(50)), (Amount::from_cents(750), Amount(25_000), Amount(-25_000))) ;
+    assert_eq!((Amount(25_000).round(2), Amount(-25_000).round(2), Amount(24_999).round(2)), (Amount::from_cents(3), Amount::from_cents(-3), Amount::from_cents(2))) ;
+
+    assert_eq!(Amount::try_from(""), Err("No number given")) ;
+    assert_eq!(Amount::try_from("1.2345678"), Err("Too many decimals")) ;
+    assert!(Amount::try_from("$12").is_err()) ;
+    assert!(Amount::try_from("1.2.3").is_err()) ;
+    assert!(Amount::try_from("999999999999999999999999999999999999999").is_err()) ;
+}
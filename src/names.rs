@@ -0,0 +1,171 @@
+/// How accented letters are treated by [`normalize_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Diacritics
+{
+    /// `"Éowyn"` and `"Eowyn"` stay different
+    Keep,
+    /// `"Éowyn"` and `"Eowyn"` are the same name
+    #[default]
+    Fold,
+}
+
+/// Normalizes a card name (or any label) for identity comparisons, folding diacritics<br/>
+/// This is the normalization used by [`crate::ids::Id`], [`crate::Classification`] and every name lookup
+/// ```
+/// use mtg_multitool::names::normalize;
+/// assert_eq!(normalize("  Éowyn,   Fearless  Knight "), "eowyn, fearless knight") ;
+/// assert_eq!(normalize("Gríma’s Whisper — Wormtongue"), "grima's whisper - wormtongue") ;
+/// ```
+pub fn normalize(s: &str) -> String
+{
+    normalize_with(s, Diacritics::Fold)
+}
+
+/// Normalizes a name:
+/// <ul>
+/// <li>Unicode case folding (`"ÉOWYN"` → `"éowyn"`)</li>
+/// <li>Diacritics removal, if asked to (`"éowyn"` → `"eowyn"`)</li>
+/// <li>Apostrophes, quotes and dashes variants are replaced by their ASCII counterpart</li>
+/// <li>Whitespaces runs are collapsed into a single space, and the result is trimmed</li>
+/// </ul>
+pub fn normalize_with(s: &str, diacritics: Diacritics) -> String
+{
+    let mut result = String::with_capacity(s.len()) ;
+    let mut pending_space = false ;
+
+    for c in s.chars()
+    {
+        if c.is_whitespace()
+        {
+            pending_space = !result.is_empty() ;
+            continue ;
+        }
+
+        if diacritics == Diacritics::Fold && is_combining_mark(c)
+        { continue ; }
+
+        if pending_space
+        {
+            result.push(' ') ;
+            pending_space = false ;
+        }
+
+        match c
+        {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' | '\u{02BC}' | '`' | '\u{00B4}' => result.push('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}' | '\u{00BB}' => result.push('"'),
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}' | '\u{2212}' => result.push('-'),
+            '\u{2026}' => result.push_str("..."),
+            'ß' | 'ẞ' => result.push_str("ss"),
+
+            c => for lower in c.to_lowercase()
+            {
+                match diacritics
+                {
+                    Diacritics::Keep => result.push(lower),
+                    Diacritics::Fold => match fold_diacritic(lower)
+                    {
+                        Some(folded) => result.push_str(folded),
+                        None => result.push(lower),
+                    },
+                }
+            },
+        }
+    }
+
+    result
+}
+
+#[test]
+fn normalize_with_tests()
+{
+    assert_eq!(normalize_with("Éowyn", Diacritics::Keep), "éowyn") ;
+    assert_eq!(normalize_with("ÉOWYN", Diacritics::Keep), normalize_with("éowyn", Diacritics::Keep)) ;
+    assert_ne!(normalize_with("Éowyn", Diacritics::Keep), normalize_with("Eowyn", Diacritics::Keep)) ;
+    assert_eq!(normalize_with("Éowyn", Diacritics::Fold), "eowyn") ;
+    assert_eq!(normalize_with("E\u{0301}owyn", Diacritics::Fold), "eowyn") ;
+
+    assert_eq!(normalize("Sméagol’s  Ring\u{00A0}–\tLost"), "smeagol's ring - lost") ;
+    assert_eq!(normalize("Lim‐Dûl"), normalize("lim-dul")) ;
+    assert_eq!(normalize("Straße"), "strasse") ;
+    assert_eq!(normalize("   "), "") ;
+}
+
+/// Checks if two names are the same once normalized
+/// ```
+/// use mtg_multitool::names::same_name;
+/// assert!(same_name("Éowyn", "éowyn")) ;
+/// assert!(same_name("Eowyn", "Éowyn")) ;
+/// assert!(!same_name("Eowyn", "Eomer")) ;
+/// ```
+pub fn same_name(a: &str, b: &str) -> bool
+{
+    normalize(a) == normalize(b)
+}
+
+/// Combining diacritical marks (U+0300 to U+036F), as found in decomposed strings
+fn is_combining_mark(c: char) -> bool
+{
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+/// Removes the diacritics of a lowercase latin letter, `None` if there is nothing to fold
+fn fold_diacritic(c: char) -> Option<&'static str>
+{
+    Some(match c
+    {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+
+        _ => return None,
+    })
+}
+
+/// Number of single character edits (insertions, deletions, substitutions) between two normalized names<br/>
+/// Used to suggest the closest known names for a misspelled one
+/// ```
+/// use mtg_multitool::names::distance;
+/// assert_eq!(distance("Bill the Pony", "bill the poney"), 1) ;
+/// assert_eq!(distance("Éowyn", "Eowyn"), 0) ;
+/// assert_eq!(distance("Frodo", "Sam"), 5) ;
+/// ```
+pub fn distance(a: &str, b: &str) -> usize
+{
+    let a: Vec<char> = normalize(a).chars().collect() ;
+    let b: Vec<char> = normalize(b).chars().collect() ;
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect() ;
+    let mut current = vec![0 ; b.len() + 1] ;
+    for (i, ca) in a.iter().enumerate()
+    {
+        current[0] = i + 1 ;
+        for (j, cb) in b.iter().enumerate()
+        {
+            let substitution = previous[j] + usize::from(ca != cb) ;
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1) ;
+        }
+        std::mem::swap(&mut previous, &mut current) ;
+    }
+
+    previous[b.len()]
+}
@@ -0,0 +1,555 @@
+use std::io::{BufRead, Write};
+
+use crate::collections::Collection;
+use crate::dates::Timestamp;
+use crate::journal::{Journal, LotChange, Origin, TagChange, Transaction};
+use crate::stock::Variant;
+
+/// Version written by [`save`], and the most recent one [`load`] understands
+pub const FORMAT_VERSION: u32 = 4 ;
+
+/// First line of every collection file
+pub const HEADER: &str = "# mtg_multitool collection" ;
+
+/// Writes the owned cards of a collection, in the following text format:
+/// ```text
+/// # mtg_multitool collection
+/// version 4
+/// LTR C 0001;2;nonfoil;NM;en;
+/// LTR C 0001;1;foil;LP;fr;Binder 2, page 5
+/// tag:trade;LTR C 0001
+/// LTR R 0004;1;nonfoil;NM;en;Box: LTR bulk
+/// Homemade card;3;nonfoil;NM;en;
+/// ```
+/// <ul>
+/// <li>The `version` line comes first, only preceded by comments and blank lines</li>
+/// <li>Each entry is `{key};{count};{finish};{condition};{language};{location}`, the key being the card's serial,
+/// or its name if it has none, the variant being written with codes (see [`Variant`]),
+/// and the location being empty for unsorted copies</li>
+/// <li>Each tag of a card is `tag:{tag};{key}`</li>
+/// <li>Lines starting with `#` are comments, blank lines are ignored</li>
+/// </ul>
+/// Cards with a count of 0 are only written if they have tags. Locations and tags cannot contain `;` nor line breaks, as collections refuse them.<br/>
+/// See [`save_journal`] to keep the transaction log of a collection along with it.<br/>
+/// Files of previous versions are still read:
+/// <ul>
+/// <li>Version 1 entries are `{key};{count}`, the copies being nonfoil and near mint, in their printing's language (see [`Variant::of`])</li>
+/// <li>Version 2 entries are `{key};{count};{finish};{condition};{language}`, the copies being unsorted</li>
+/// <li>Version 3 files have no transaction log</li>
+/// </ul>
+pub fn save<W: Write>(collection: &Collection<'_>, writer: W) -> std::io::Result<()>
+{
+    write(collection, &[], writer)
+}
+
+/// Writes the collection of a journal as [`save`] does, followed by its transactions, oldest first:
+/// ```text
+/// transaction:2026-01-01T12:30:00Z;LTR C 0001;manual;moved to the trade binder
+/// change:-2;nonfoil;NM;en;
+/// change:+2;nonfoil;NM;en;Trade binder
+/// tagged:+trade
+/// ```
+/// <ul>
+/// <li>Each transaction is `transaction:{time};{key};{source};{reason}`, the source not containing `;` either,
+/// and neither of them containing line breaks</li>
+/// <li>It is followed by its changes, `change:{delta};{finish};{condition};{language};{location}`</li>
+/// <li>Then by the tags it added, `tagged:+{tag}`, and removed, `tagged:-{tag}`</li>
+/// </ul>
+/// Undone transactions are not written.
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::journal::{Journal, Origin};
+/// use mtg_multitool::{ltr, persistence};
+///
+/// let mut journal = Journal::new(Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 2)]).unwrap()) ;
+/// journal.add("LTR C 0001", 1, Origin::now("manual", "opened a booster")).unwrap() ;
+/// let mut file = vec![] ;
+/// persistence::save_journal(&journal, &mut file).unwrap() ;
+///
+/// let (loaded, report) = persistence::load_journal(&ltr::collection(), file.as_slice()).unwrap() ;
+/// assert!(report.is_complete()) ;
+/// assert_eq!(loaded.collection().count("LTR C 0001"), Some(3)) ;
+/// assert_eq!(loaded.transactions()[0].origin.reason, "opened a booster") ;
+/// ```
+pub fn save_journal<W: Write>(journal: &Journal<'_>, writer: W) -> std::io::Result<()>
+{
+    write(journal.collection(), journal.transactions(), writer)
+}
+
+fn write<W: Write>(collection: &Collection<'_>, transactions: &[Transaction<'_>], mut writer: W) -> std::io::Result<()>
+{
+    writeln!(writer, "{}", HEADER)? ;
+    writeln!(writer, "version {}", FORMAT_VERSION)? ;
+
+    for (card, stock) in collection.stocks()
+    {
+        let key = card.printing().serial().unwrap_or(*card.name()) ;
+        for lot in stock.lots()
+        {
+            let variant = &lot.variant ;
+            writeln!(
+                writer, "{};{};{};{};{};{}",
+                key, lot.count, variant.finish.code(), variant.condition.code(), variant.language.code(), lot.location.as_deref().unwrap_or_default()
+            )? ;
+        }
+        for tag in stock.tags()
+        {
+            writeln!(writer, "{}{};{}", TAG_PREFIX, tag, key)? ;
+        }
+    }
+
+    for transaction in transactions
+    {
+        let (card, origin) = (&transaction.card, &transaction.origin) ;
+        let key = card.printing().serial().unwrap_or(*card.name()) ;
+        writeln!(writer, "{}{};{};{};{}", TRANSACTION_PREFIX, origin.time, key, origin.source, origin.reason)? ;
+        for change in &transaction.changes
+        {
+            let variant = &change.variant ;
+            writeln!(
+                writer, "{}{:+};{};{};{};{}",
+                CHANGE_PREFIX, change.delta, variant.finish.code(), variant.condition.code(), variant.language.code(), change.location.as_deref().unwrap_or_default()
+            )? ;
+        }
+        for change in &transaction.tags
+        {
+            writeln!(writer, "{}{}{}", TAG_CHANGE_PREFIX, if change.added { '+' } else { '-' }, change.tag)? ;
+        }
+    }
+
+    writer.flush()
+}
+
+/// Reads a collection written by [`save`], resolving its entries against a catalog (such as `ltr::collection()`)<br/>
+/// Entries which do not resolve to exactly one card of the catalog are left out, and listed in the report
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::{ltr, persistence};
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 2)]).unwrap() ;
+/// let mut file = vec![] ;
+/// persistence::save(&collection, &mut file).unwrap() ;
+///
+/// let (loaded, report) = persistence::load(&ltr::collection(), file.as_slice()).unwrap() ;
+/// assert_eq!(loaded.count("LTR C 0001"), Some(2)) ;
+/// assert!(report.is_complete()) ;
+/// ```
+pub fn load<'gc, R: BufRead>(catalog: &Collection<'gc>, reader: R) -> Result<(Collection<'gc>, LoadReport), PersistError>
+{
+    read(catalog, reader).map(|(collection, _, report)| (collection, report))
+}
+
+/// Reads a collection and its transaction log written by [`save_journal`], as [`load`] does<br/>
+/// Transactions of cards which do not resolve are left out, and listed in the report. Files without log are read as an empty log
+pub fn load_journal<'gc, R: BufRead>(catalog: &Collection<'gc>, reader: R) -> Result<(Journal<'gc>, LoadReport), PersistError>
+{
+    read(catalog, reader).map(|(collection, transactions, report)| (Journal::with_transactions(collection, transactions), report))
+}
+
+fn read<'gc, R: BufRead>(catalog: &Collection<'gc>, reader: R) -> Result<(Collection<'gc>, Vec<Transaction<'gc>>, LoadReport), PersistError>
+{
+    let mut transactions: Vec<Transaction<'gc>> = vec![] ;
+    // Whether change lines are expected, and whether they belong to the last transaction (`Some(false)` for left out ones)
+    let mut receiving: Option<bool> = None ;
+    let mut collection = Collection::from_counts(catalog, std::iter::empty::<(&str, usize)>())
+        .expect("An empty list of counts always resolves") ;
+    let mut report = LoadReport::default() ;
+    let mut version = None ;
+
+    for (index, line) in reader.lines().enumerate()
+    {
+        let line = line? ;
+        let number = index + 1 ;
+        let content = line.trim() ;
+        if content.is_empty() || content.starts_with('#') { continue ; }
+
+        let version = match version
+        {
+            Some(version) => version,
+            None => {
+                let found = parse_version(content).ok_or(PersistError::MissingVersion)? ;
+                if found > FORMAT_VERSION { return Err(PersistError::UnsupportedVersion(found)) ; }
+                report.version = found ;
+                version = Some(found) ;
+                continue ;
+            },
+        } ;
+
+        let entry = parse_entry(content, version).map_err(|reason| PersistError::Malformed { line: number, reason })? ;
+        let (key, count, result) = match entry
+        {
+            Entry::Copies { key, count, variant: None, location: _ } => (key, count, collection.add(key, count).map(|_| ())),
+            Entry::Copies { key, count, variant: Some(variant), location: None } => (key, count, collection.add_variant(key, variant, count).map(|_| ())),
+            Entry::Copies { key, count, variant: Some(variant), location: Some(location) } => (key, count, collection.add_at(key, variant, location, count).map(|_| ())),
+            Entry::Tag { key, tag } => (key, 0, collection.tag(key, tag).map(|_| ())),
+            Entry::Transaction { key, origin } => {
+                let card = collection.resolve(key, collection.policy()).cloned().map_err(Into::into) ;
+                receiving = Some(card.is_ok()) ;
+                (key, 0, card.map(|card| transactions.push(Transaction { card, origin, changes: vec![], tags: vec![] })))
+            },
+            Entry::Change(change) => {
+                if let Some(transaction) = receiver(receiving, &mut transactions, number)?
+                {
+                    transaction.changes.push(change) ;
+                }
+                continue ;
+            },
+            Entry::TagChange(change) => {
+                if let Some(transaction) = receiver(receiving, &mut transactions, number)?
+                {
+                    transaction.tags.push(change) ;
+                }
+                continue ;
+            },
+        } ;
+        if let Err(e) = result
+        {
+            report.unresolved.push(UnresolvedEntry { line: number, key: key.to_string(), count, reason: e.to_string() }) ;
+        }
+    }
+
+    match version
+    {
+        Some(_) => Ok((collection, transactions, report)),
+        None => Err(PersistError::MissingVersion),
+    }
+}
+
+/// Transaction receiving the changes being read, `None` for those of a transaction left out
+fn receiver<'t, 'gc>(receiving: Option<bool>, transactions: &'t mut [Transaction<'gc>], line: usize) -> Result<Option<&'t mut Transaction<'gc>>, PersistError>
+{
+    match (receiving, transactions.last_mut())
+    {
+        (Some(true), transaction) => Ok(transaction),
+        (Some(false), _) => Ok(None),
+        (None, _) => Err(PersistError::Malformed { line, reason: "Change outside of a transaction" }),
+    }
+}
+
+fn parse_version(line: &str) -> Option<u32>
+{
+    line.strip_prefix("version")?.trim().parse().ok()
+}
+
+/// Prefix of the lines tagging a card
+const TAG_PREFIX: &str = "tag:" ;
+
+/// Prefix of the lines starting a transaction, followed by its changes
+const TRANSACTION_PREFIX: &str = "transaction:" ;
+
+/// Prefix of the lines of a transaction's changes
+const CHANGE_PREFIX: &str = "change:" ;
+
+/// Prefix of the lines of a transaction's tag changes
+const TAG_CHANGE_PREFIX: &str = "tagged:" ;
+
+/// A line of a collection file
+enum Entry<'l>
+{
+    /// Copies of the card's default variant (see [`Variant::of`]) when no variant is given
+    Copies { key: &'l str, count: usize, variant: Option<Variant>, location: Option<&'l str> },
+    Tag { key: &'l str, tag: &'l str },
+    Transaction { key: &'l str, origin: Origin },
+    Change(LotChange),
+    TagChange(TagChange),
+}
+
+/// Parses an entry line, as written in the given version of the format
+fn parse_entry(line: &str, version: u32) -> Result<Entry<'_>, &'static str>
+{
+    match version
+    {
+        1 => {
+            let (key, count) = line.rsplit_once(';').ok_or("Expected `key;count`")? ;
+            Ok(Entry::Copies { key: parse_key(key)?, count: parse_count(count)?, variant: None, location: None })
+        },
+        2 => {
+            let (key, count, variant) = parse_copies(line)? ;
+            Ok(Entry::Copies { key, count, variant: Some(variant), location: None })
+        },
+        4 if line.starts_with(TRANSACTION_PREFIX) => {
+            let mut fields = line[TRANSACTION_PREFIX.len()..].splitn(4, ';') ;
+            match (fields.next(), fields.next(), fields.next(), fields.next())
+            {
+                (Some(time), Some(key), Some(source), Some(reason)) => {
+                    let time = Timestamp::try_from(time)? ;
+                    Ok(Entry::Transaction { key: parse_key(key)?, origin: Origin::at(time, source, reason) })
+                },
+                _ => Err("Expected `transaction:time;key;source;reason`"),
+            }
+        },
+        4 if line.starts_with(TAG_CHANGE_PREFIX) => {
+            let change = &line[TAG_CHANGE_PREFIX.len()..] ;
+            let (added, tag) = match (change.strip_prefix('+'), change.strip_prefix('-'))
+            {
+                (Some(tag), _) => (true, tag),
+                (_, Some(tag)) => (false, tag),
+                _ => return Err("Expected `tagged:+tag` or `tagged:-tag`"),
+            } ;
+            Ok(Entry::TagChange(TagChange { tag: tag.trim().to_string(), added }))
+        },
+        4 if line.starts_with(CHANGE_PREFIX) => {
+            let (copies, location) = line[CHANGE_PREFIX.len()..].rsplit_once(';').ok_or("Expected `change:delta;finish;condition;language;location`")? ;
+            let mut fields = copies.splitn(4, ';') ;
+            let (delta, finish, condition, language) = match (fields.next(), fields.next(), fields.next(), fields.next())
+            {
+                (Some(delta), Some(finish), Some(condition), Some(language)) => (delta, finish, condition, language),
+                _ => return Err("Expected `change:delta;finish;condition;language;location`"),
+            } ;
+            let delta = delta.trim().parse().map_err(|_| "Could not parse change")? ;
+            let variant = Variant::new(finish.try_into()?, condition.try_into()?, language.try_into()?) ;
+            let location = Some(location.trim()).filter(|location| !location.is_empty()).map(str::to_string) ;
+            Ok(Entry::Change(LotChange { variant, location, delta }))
+        },
+        3 | 4 => match line.strip_prefix(TAG_PREFIX)
+        {
+            Some(tag) => {
+                let (tag, key) = tag.split_once(';').ok_or("Expected `tag:tag;key`")? ;
+                Ok(Entry::Tag { key: parse_key(key)?, tag: tag.trim() })
+            },
+            None => {
+                let (copies, location) = line.rsplit_once(';').ok_or("Expected `key;count;finish;condition;language;location`")? ;
+                let (key, count, variant) = parse_copies(copies)? ;
+                let location = Some(location.trim()).filter(|location| !location.is_empty()) ;
+                Ok(Entry::Copies { key, count, variant: Some(variant), location })
+            },
+        },
+        _ => Err("Unknown format version"),
+    }
+}
+
+/// Parses `key;count;finish;condition;language`
+fn parse_copies(line: &str) -> Result<(&str, usize, Variant), &'static str>
+{
+    let mut fields = line.rsplitn(5, ';') ;
+    let (language, condition, finish, count, key) = match (fields.next(), fields.next(), fields.next(), fields.next(), fields.next())
+    {
+        (Some(language), Some(condition), Some(finish), Some(count), Some(key)) => (language, condition, finish, count, key),
+        _ => return Err("Expected `key;count;finish;condition;language`"),
+    } ;
+    let variant = Variant::new(finish.try_into()?, condition.try_into()?, language.try_into()?) ;
+    Ok((parse_key(key)?, parse_count(count)?, variant))
+}
+
+fn parse_key(key: &str) -> Result<&str, &'static str>
+{
+    let key = key.trim() ;
+    if key.is_empty() { return Err("No card given") ; }
+    Ok(key)
+}
+
+fn parse_count(count: &str) -> Result<usize, &'static str>
+{
+    count.trim().parse().map_err(|_| "Could not parse count")
+}
+
+/// What happened while loading a collection file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadReport
+{
+    /// Version of the format the file was written in
+    pub version: u32,
+    /// Entries which were left out
+    pub unresolved: Vec<UnresolvedEntry>,
+}
+
+impl LoadReport
+{
+    /// Whether every entry of the file was loaded
+    pub fn is_complete(&self) -> bool
+    {
+        self.unresolved.is_empty()
+    }
+}
+
+/// An entry of a collection file which does not resolve to a card of the catalog
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedEntry
+{
+    /// Line number, starting at 1
+    pub line: usize,
+    pub key: String,
+    pub count: usize,
+    pub reason: String,
+}
+
+/// Why a collection file could not be read
+#[derive(Debug)]
+pub enum PersistError
+{
+    Io(std::io::Error),
+    /// The file has no `version` line before its first entry
+    MissingVersion,
+    /// The file was written by a newer version of the format
+    UnsupportedVersion(u32),
+    /// An entry could not be parsed
+    Malformed { line: usize, reason: &'static str },
+}
+
+impl From<std::io::Error> for PersistError
+{
+    fn from(value: std::io::Error) -> Self
+    {
+        PersistError::Io(value)
+    }
+}
+
+impl std::fmt::Display for PersistError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            PersistError::Io(e) => write!(f, "Could not read the collection file: {}", e),
+            PersistError::MissingVersion => write!(f, "The collection file has no version line"),
+            PersistError::UnsupportedVersion(version) => write!(f, "Version {} of the collection format is not supported (latest is {})", version, FORMAT_VERSION),
+            PersistError::Malformed { line, reason } => write!(f, "Line {}: {}", line, reason),
+        }
+    }
+}
+
+impl std::error::Error for PersistError
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self
+        {
+            PersistError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn persistence_round_trip()
+{
+    let catalog = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        "LTR R 0004;Boromir, Warden of the Tower;2 w;Creature - Human Soldier;R;",
+        ";Homemade card;1;Sorcery;C;",
+    ]).unwrap() ;
+    let mut collection = Collection::from_counts(&catalog, [("Banish from Edoras", 2), ("Homemade card", 3)]).unwrap() ;
+    let foil = Variant::new(crate::Finish::Foil, crate::Condition::LightlyPlayed, crate::Language::French) ;
+    collection.add_at("Banish from Edoras", foil, "Binder 2, page 5", 1).unwrap() ;
+    collection.tag("Banish from Edoras", "trade").unwrap() ;
+    collection.tag("LTR R 0004", "wanted").unwrap() ;
+
+    let mut file = vec![] ;
+    save(&collection, &mut file).unwrap() ;
+    assert_eq!(
+        String::from_utf8(file.clone()).unwrap(),
+        "# mtg_multitool collection\nversion 4\n\
+         LTR C 0001;2;nonfoil;NM;en;\nLTR C 0001;1;foil;LP;fr;Binder 2, page 5\ntag:trade;LTR C 0001\n\
+         tag:wanted;LTR R 0004\n\
+         Homemade card;3;nonfoil;NM;en;\n"
+    ) ;
+
+    let (loaded, report) = load(&catalog, file.as_slice()).unwrap() ;
+    assert_eq!(report, LoadReport { version: 4, unresolved: vec![] }) ;
+    assert_eq!(loaded.stock("LTR R 0004"), collection.stock("LTR R 0004")) ;
+    assert!(collection.diff(&loaded).is_empty()) ;
+    assert_eq!(loaded.stock("LTR C 0001"), collection.stock("LTR C 0001")) ;
+    assert_eq!(loaded.len(), 3) ;
+
+    // Labels spanning several lines are refused, so that the file can still be read back
+    assert!(collection.add_at("Banish from Edoras", foil, "Binder 2\npage 5", 1).is_err()) ;
+    assert!(collection.move_copies("Banish from Edoras", None, Some("Binder 2\r\npage 5"), 1).is_err()) ;
+    assert_eq!(collection.tag("Banish from Edoras", "for\ntrade"), Err(crate::collections::CollectionError::ReservedCharacter("for\ntrade".to_string()))) ;
+    let mut file = vec![] ;
+    save(&collection, &mut file).unwrap() ;
+    let (loaded, report) = load(&catalog, file.as_slice()).unwrap() ;
+    assert!(report.is_complete()) ;
+    assert!(collection.diff(&loaded).is_empty()) ;
+    assert_eq!(loaded.locations(), vec!["Binder 2, page 5"]) ;
+}
+
+#[test]
+fn persistence_journal()
+{
+    let catalog = Collection::make(vec![
+        "LTR C 0001;Banish from Edoras;4 w;Sorcery;C;",
+        ";Homemade card;1;Sorcery;C;",
+    ]).unwrap() ;
+    let mut journal = Journal::new(Collection::from_counts(&catalog, [("Banish from Edoras", 2)]).unwrap()) ;
+    let time = Timestamp::try_from("2026-01-01 12:30").unwrap() ;
+    journal.move_copies("LTR C 0001", None, Some("Trade binder"), 2, Origin::at(time, "manual", "for trade; maybe")).unwrap() ;
+    journal.add("Homemade card", 1, Origin::at(time.plus(60), "import", "")).unwrap() ;
+    journal.tag("Homemade card", "proxy", Origin::at(time.plus(120), "manual", "")).unwrap() ;
+    assert!(journal.add("Homemade card", 1, Origin::at(time.plus(180), "manual", "pasted note\r\nsecond line")).is_err()) ;
+
+    let mut file = vec![] ;
+    save_journal(&journal, &mut file).unwrap() ;
+    let text = String::from_utf8(file.clone()).unwrap() ;
+    assert!(text.ends_with(
+        "transaction:2026-01-01T12:30:00Z;LTR C 0001;manual;for trade; maybe\n\
+         change:-2;nonfoil;NM;en;\nchange:+2;nonfoil;NM;en;Trade binder\n\
+         transaction:2026-01-01T12:31:00Z;Homemade card;import;\nchange:+1;nonfoil;NM;en;\n\
+         transaction:2026-01-01T12:32:00Z;Homemade card;manual;\ntagged:+proxy\n"
+    ), "{}", text) ;
+
+    let (mut loaded, report) = load_journal(&catalog, file.as_slice()).unwrap() ;
+    assert!(report.is_complete()) ;
+    assert!(journal.collection().diff(loaded.collection()).is_empty()) ;
+    assert_eq!(loaded.transactions().iter().map(|transaction| transaction.to_string()).collect::<Vec<_>>(),
+               journal.transactions().iter().map(|transaction| transaction.to_string()).collect::<Vec<_>>()) ;
+    assert_eq!(loaded.at(time).count("Homemade card"), Some(0)) ;
+    assert_eq!(loaded.transactions()[2].tags, vec![TagChange { tag: "proxy".to_string(), added: true }]) ;
+    loaded.undo() ;
+    assert!(!loaded.collection().stock("Homemade card").unwrap().has_tag("proxy")) ;
+    loaded.undo() ;
+    loaded.undo() ;
+    assert_eq!(loaded.collection().stock("LTR C 0001").map(|stock| stock.at(None)), Some(2)) ;
+
+    // A plain collection file is a journal without transactions, and a journal file is a collection
+    assert!(load_journal(&catalog, "version 3\nLTR C 0001;2;nonfoil;NM;en;\n".as_bytes()).unwrap().0.transactions().is_empty()) ;
+    assert_eq!(load(&catalog, file.as_slice()).unwrap().0.count("Homemade card"), Some(1)) ;
+
+    // Transactions of unknown cards are left out along with their changes
+    let file = "version 4\ntransaction:2026-01-01;Sauron;manual;\nchange:+1;nonfoil;NM;en;\ntransaction:2026-01-02;LTR C 0001;manual;\nchange:+1;nonfoil;NM;en;\n" ;
+    let (loaded, report) = load_journal(&catalog, file.as_bytes()).unwrap() ;
+    assert_eq!(report.unresolved.iter().map(|entry| (entry.line, entry.key.as_str())).collect::<Vec<_>>(), vec![(2, "Sauron")]) ;
+    assert_eq!(loaded.transactions().iter().map(Transaction::quantity).collect::<Vec<_>>(), vec![1]) ;
+
+    assert!(matches!(load_journal(&catalog, "version 4\nchange:+1;nonfoil;NM;en;\n".as_bytes()), Err(PersistError::Malformed { line: 2, .. }))) ;
+    assert!(matches!(load_journal(&catalog, "version 4\ntransaction:yesterday;LTR C 0001;manual;\n".as_bytes()), Err(PersistError::Malformed { line: 2, .. }))) ;
+    assert!(matches!(load(&catalog, "version 3\ntransaction:2026-01-01;LTR C 0001;manual;\n".as_bytes()), Err(PersistError::Malformed { line: 2, .. }))) ;
+}
+
+#[test]
+fn persistence_load()
+{
+    let catalog = crate::ltr::collection() ;
+
+    let file = "\n# Kept between runs\nversion 1\nLTR C 0001 ; 2\n\nLTR R 0004;1\nLTR C 0001;1\nXYZ C 0001;4\nHomemade card;3\n" ;
+    let (loaded, report) = load(&catalog, file.as_bytes()).unwrap() ;
+    assert_eq!(loaded.count("Banish from Edoras"), Some(3)) ;
+    assert_eq!(loaded.count("LTR R 0004"), Some(1)) ;
+    assert_eq!(loaded.total(), 4) ;
+    assert!(!report.is_complete()) ;
+    assert_eq!(report.unresolved.iter().map(|entry| (entry.line, entry.key.as_str(), entry.count)).collect::<Vec<_>>(),
+               vec![(8, "XYZ C 0001", 4), (9, "Homemade card", 3)]) ;
+
+    assert!(matches!(load(&catalog, "LTR C 0001;2\n".as_bytes()), Err(PersistError::MissingVersion))) ;
+    assert!(matches!(load(&catalog, "# mtg_multitool collection\n".as_bytes()), Err(PersistError::MissingVersion))) ;
+    assert!(matches!(load(&catalog, "version 99\nLTR C 0001;2\n".as_bytes()), Err(PersistError::UnsupportedVersion(99)))) ;
+    assert!(matches!(load(&catalog, "version 1\nLTR C 0001\n".as_bytes()), Err(PersistError::Malformed { line: 2, .. }))) ;
+    assert!(matches!(load(&catalog, "version 1\nLTR C 0001;two\n".as_bytes()), Err(PersistError::Malformed { line: 2, .. }))) ;
+    assert!(matches!(load(&catalog, "version 2\nLTR C 0001;2\n".as_bytes()), Err(PersistError::Malformed { line: 2, .. }))) ;
+    assert!(matches!(load(&catalog, "version 3\ntag:trade\n".as_bytes()), Err(PersistError::Malformed { line: 2, .. }))) ;
+    assert!(matches!(load(&catalog, "version 2\nLTR C 0001;2;shiny;NM;en\n".as_bytes()), Err(PersistError::Malformed { line: 2, reason: "Could not parse into finish" }))) ;
+
+    // Version 1 files are migrated to nonfoil, near mint copies in the printing's language, English for LTR
+    let (loaded, report) = load(&catalog, "version 1\nLTR C 0001;2\n".as_bytes()).unwrap() ;
+    assert_eq!(report.version, 1) ;
+    assert_eq!(loaded.count_variant("LTR C 0001", &Variant::default()), Some(2)) ;
+
+    // As well as version 2 files, as unsorted copies
+    let (loaded, report) = load(&catalog, "version 2\nLTR C 0001;2;foil;NM;en\n".as_bytes()).unwrap() ;
+    assert_eq!(report.version, 2) ;
+    assert_eq!(loaded.stock("LTR C 0001").map(|stock| stock.at(None)), Some(2)) ;
+
+    let (loaded, report) = load(&catalog, "version 3\nLTR C 0001;2;foil;NM;en;Box 1\ntag:trade;LTR C 0001\ntag:trade;Sauron\n".as_bytes()).unwrap() ;
+    assert_eq!(loaded.stock("LTR C 0001").map(|stock| stock.at(Some("box 1"))), Some(2)) ;
+    assert_eq!(loaded.tagged("trade").count(), 1) ;
+    assert_eq!(report.unresolved.iter().map(|entry| (entry.line, entry.key.as_str())).collect::<Vec<_>>(), vec![(4, "Sauron")]) ;
+}
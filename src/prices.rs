@@ -0,0 +1,498 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::cards::GenericCard;
+use crate::collections::{serial_key, Collection};
+use crate::csv::field;
+use crate::dates::Timestamp;
+use crate::imports::ImportError;
+use crate::exchange::{Conversion, Target};
+use crate::money::{Amount, Currency, Money};
+use crate::stock::Variant;
+use crate::{Condition, Finish, Rarity};
+
+/// Gives the price of a copy of a variant of a card, in the currency it is quoted in, if known<br/>
+/// Implemented by closures, such as `|card: &GenericCard, variant: &Variant| prices.get(card.name())`
+pub trait PriceSource
+{
+    fn price(&self, card: &GenericCard<'_>, variant: &Variant) -> Option<Money> ;
+}
+
+impl<F> PriceSource for F where F: Fn(&GenericCard<'_>, &Variant) -> Option<Money>
+{
+    fn price(&self, card: &GenericCard<'_>, variant: &Variant) -> Option<Money>
+    {
+        self(card, variant)
+    }
+}
+
+/// No price data: trades are not balanced by value, and excess copies are unpriced
+pub fn no_prices(_: &GenericCard<'_>, _: &Variant) -> Option<Money>
+{
+    None
+}
+
+/// Price of a printing in a finish, as of a date
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote
+{
+    /// Serial of the printing, such as `"LTR C 0001"` or `"LTR-1"`
+    pub serial: String,
+    pub finish: Finish,
+    /// Price of a near mint copy
+    pub price: Amount,
+    pub currency: Currency,
+    pub date: Timestamp,
+}
+
+/// Column titles of price snapshots, the first one being the expected one
+const SERIAL_COLUMN: [&str ; 2] = ["Serial", "Id"] ;
+const FINISH_COLUMN: [&str ; 2] = ["Finish", "Foil"] ;
+const PRICE_COLUMN: [&str ; 1] = ["Price"] ;
+const CURRENCY_COLUMN: [&str ; 1] = ["Currency"] ;
+const DATE_COLUMN: [&str ; 1] = ["Date"] ;
+
+/// Prices read from local snapshots, such as the csv exports of a shop<br/>
+/// Copies are priced from the latest quote of their printing and finish, in its currency,
+/// scaled by the multiplier of their condition (100% for every condition by default)
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::exchange::ExchangeRates;
+/// use mtg_multitool::money::{Amount, Currency};
+/// use mtg_multitool::prices::{value, PriceDb};
+/// use mtg_multitool::{ltr, Condition};
+///
+/// let snapshot = "serial,finish,price,currency,date\n\
+///                 LTR C 0001,nonfoil,0.25,EUR,2026-09-01\n\
+///                 LTR C 0001,nonfoil,0.30,EUR,2026-10-01\n\
+///                 LTR R 0004,foil,3.50,EUR,2026-10-01\n" ;
+/// let prices = PriceDb::from_csv(snapshot).unwrap().with_multiplier(Condition::LightlyPlayed, 80) ;
+/// assert_eq!(prices.latest("LTR-1", Default::default()).map(|quote| quote.price), Some(Amount::from_cents(30))) ;
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4), ("LTR R 0004", 1)]).unwrap() ;
+/// let rates = ExchangeRates::default() ;
+/// let valuation = value(&collection, &prices, rates.to(Currency::try_from("EUR").unwrap(), Timestamp::now())) ;
+/// assert_eq!((valuation.total(), valuation.unpriced()), (Amount::from_cents(120), 1)) ;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PriceDb
+{
+    quotes: Vec<Quote>,
+    /// Serial keys (see [`serial_key`]) and finishes to positions in `quotes`
+    by_printing: HashMap<(String, Finish), Vec<usize>>,
+    /// Percentage of the near mint price, for each condition from near mint to damaged
+    multipliers: [u32 ; 5],
+}
+
+impl PriceDb
+{
+    /// Reads a csv snapshot with `serial`, `price`, `currency` and `date` columns, and an optional `finish` one
+    pub fn from_csv(text: &str) -> Result<PriceDb, ImportError>
+    {
+        let mut records = crate::csv::records(text, ',')
+            .map_err(|(line, reason)| ImportError::Malformed { line, reason })?
+            .into_iter() ;
+        let (_, header) = records.next().ok_or(ImportError::MissingColumn(SERIAL_COLUMN[0]))? ;
+
+        let find = |aliases: &[&'static str]| header.iter().position(|title| aliases.iter().any(|alias| crate::names::same_name(title, alias))) ;
+        let serial = find(&SERIAL_COLUMN).ok_or(ImportError::MissingColumn(SERIAL_COLUMN[0]))? ;
+        let price = find(&PRICE_COLUMN).ok_or(ImportError::MissingColumn(PRICE_COLUMN[0]))? ;
+        let currency = find(&CURRENCY_COLUMN).ok_or(ImportError::MissingColumn(CURRENCY_COLUMN[0]))? ;
+        let date = find(&DATE_COLUMN).ok_or(ImportError::MissingColumn(DATE_COLUMN[0]))? ;
+        let finish = find(&FINISH_COLUMN) ;
+
+        let mut prices = PriceDb::default() ;
+        for (line, fields) in records
+        {
+            let field = |index: Option<usize>| index.and_then(|index| fields.get(index)).map(String::as_str).unwrap_or_default() ;
+            let malformed = |reason: &'static str| ImportError::Malformed { line, reason } ;
+
+            if field(Some(serial)).is_empty() { return Err(malformed("No serial given")) ; }
+            prices.push(Quote
+            {
+                serial: field(Some(serial)).to_string(),
+                finish: Finish::try_from(field(finish)).map_err(malformed)?,
+                price: Amount::try_from(field(Some(price))).map_err(malformed)?,
+                currency: Currency::try_from(field(Some(currency))).map_err(malformed)?,
+                date: Timestamp::try_from(field(Some(date))).map_err(malformed)?,
+            }) ;
+        }
+        Ok(prices)
+    }
+
+    pub fn push(&mut self, quote: Quote)
+    {
+        self.by_printing.entry((serial_key(&quote.serial), quote.finish)).or_default().push(self.quotes.len()) ;
+        self.quotes.push(quote) ;
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.quotes.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.quotes.is_empty()
+    }
+
+    /// Quotes in the order they were read
+    pub fn quotes(&self) -> std::slice::Iter<'_, Quote>
+    {
+        self.quotes.iter()
+    }
+
+    /// Adds the quotes of another snapshot, such as a later export of the same shop
+    pub fn merge(&mut self, other: PriceDb)
+    {
+        for quote in other.quotes
+        {
+            self.push(quote) ;
+        }
+    }
+
+    /// Days of the snapshots, from the oldest
+    pub fn dates(&self) -> Vec<Timestamp>
+    {
+        let mut dates: Vec<Timestamp> = self.quotes.iter().map(|quote| quote.date).collect() ;
+        dates.sort() ;
+        dates.dedup() ;
+        dates
+    }
+
+    /// Latest quote of a printing in a finish; `"LTR C 0001"` and `"LTR-1"` are the same printing
+    pub fn latest(&self, serial: &str, finish: Finish) -> Option<&Quote>
+    {
+        self.quote_at(serial, finish, Timestamp::from_seconds(u64::MAX))
+    }
+
+    /// Latest quote of a printing in a finish as of a date, that is the latest one not after it
+    pub fn quote_at(&self, serial: &str, finish: Finish, date: Timestamp) -> Option<&Quote>
+    {
+        self.by_printing.get(&(serial_key(serial), finish))?
+            .iter()
+            .map(|&position| &self.quotes[position])
+            .filter(|quote| quote.date <= date)
+            .max_by_key(|quote| quote.date)
+    }
+
+    /// The prices as they were on a date, ignoring later snapshots
+    /// ```
+    /// use mtg_multitool::dates::Timestamp;
+    /// use mtg_multitool::exchange::ExchangeRates;
+    /// use mtg_multitool::money::Currency;
+    /// use mtg_multitool::prices::{value, PriceDb};
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::ltr;
+    ///
+    /// let mut prices = PriceDb::from_csv("serial,price,currency,date\nLTR C 0001,0.25,EUR,2026-09-01\n").unwrap() ;
+    /// prices.merge(PriceDb::from_csv("serial,price,currency,date\nLTR C 0001,0.40,EUR,2026-10-01\n").unwrap()) ;
+    ///
+    /// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4)]).unwrap() ;
+    /// let rates = ExchangeRates::default() ;
+    /// let euros = rates.to(Currency::try_from("EUR").unwrap(), Timestamp::now()) ;
+    /// let september = prices.as_of(Timestamp::from_date(2026, 9, 15).unwrap()) ;
+    /// assert_eq!(value(&collection, &september, euros).total().to_string(), "1.00") ;
+    /// assert_eq!(value(&collection, &prices, euros).total().to_string(), "1.60") ;
+    /// ```
+    pub fn as_of(&self, date: Timestamp) -> AsOf<'_>
+    {
+        AsOf { prices: self, date }
+    }
+
+    /// Writes every quote as a csv snapshot, which [`PriceDb::from_csv`] reads back
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()>
+    {
+        writeln!(writer, "{},{},{},{},{}", SERIAL_COLUMN[0], FINISH_COLUMN[0], PRICE_COLUMN[0], CURRENCY_COLUMN[0], DATE_COLUMN[0])? ;
+        for quote in &self.quotes
+        {
+            let date = if quote.date.seconds() % (24 * 3600) == 0 { quote.date.date_string() } else { quote.date.to_string() } ;
+            writeln!(writer, "{},{},{},{},{}", field(&quote.serial, ','), quote.finish.code(), quote.price, quote.currency, date)? ;
+        }
+        Ok(())
+    }
+
+    /// Prices copies of a condition at a percentage of the near mint price
+    pub fn with_multiplier(mut self, condition: Condition, percent: u32) -> PriceDb
+    {
+        self.multipliers[condition as usize] = percent ;
+        self
+    }
+
+    pub fn multiplier(&self, condition: Condition) -> u32
+    {
+        self.multipliers[condition as usize]
+    }
+}
+
+impl Default for PriceDb
+{
+    fn default() -> Self
+    {
+        PriceDb { quotes: vec![], by_printing: HashMap::new(), multipliers: [100 ; 5] }
+    }
+}
+
+impl PriceSource for PriceDb
+{
+    fn price(&self, card: &GenericCard<'_>, variant: &Variant) -> Option<Money>
+    {
+        self.as_of(Timestamp::from_seconds(u64::MAX)).price(card, variant)
+    }
+}
+
+/// Prices of a database as of a date, see [`PriceDb::as_of`]
+#[derive(Debug, Clone, Copy)]
+pub struct AsOf<'p>
+{
+    pub prices: &'p PriceDb,
+    pub date: Timestamp,
+}
+
+impl PriceSource for AsOf<'_>
+{
+    fn price(&self, card: &GenericCard<'_>, variant: &Variant) -> Option<Money>
+    {
+        let quote = self.prices.quote_at(card.printing().serial()?, variant.finish, self.date)? ;
+        Some(Money::new(quote.price.percent(self.prices.multiplier(variant.condition)), quote.currency))
+    }
+}
+
+/// Copies of a variant of a card, along with the price of a copy (`None` if unpriced)
+#[derive(Debug, Clone)]
+pub struct Valued<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub variant: Variant,
+    pub count: usize,
+    /// In the currency it is quoted in
+    pub price: Option<Money>,
+}
+
+impl Valued<'_>
+{
+    pub fn value(&self) -> Option<Money>
+    {
+        self.price.map(|price| price * self.count)
+    }
+}
+
+/// Prices every copy of a collection in a currency, by card and variant<br/>
+/// Copies quoted in a currency without exchange rate to the target one are unpriced
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::exchange::ExchangeRates;
+/// use mtg_multitool::money::Currency;
+/// use mtg_multitool::prices::{value, PriceDb};
+/// use mtg_multitool::ltr;
+///
+/// let prices = PriceDb::from_csv("serial,price,currency,date\n\
+///                                 LTR C 0001,0.50,EUR,2026-10-01\n\
+///                                 LTR R 0004,2.00,USD,2026-10-01\n").unwrap() ;
+/// let rates = ExchangeRates::from_csv("date,from,to,rate\n2026-10-01,EUR,USD,1.10\n").unwrap() ;
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 4), ("LTR R 0004", 1)]).unwrap() ;
+///
+/// let valuation = value(&collection, &prices, rates.to(Currency::try_from("USD").unwrap(), Timestamp::now())) ;
+/// assert_eq!(valuation.to_string(), "Total: 4.20 USD\nRate: 1 EUR = 1.1 USD (2026-10-01)\n") ;
+/// ```
+pub fn value<'gc, P: PriceSource>(collection: &Collection<'gc>, prices: &P, target: Target<'_>) -> Valuation<'gc>
+{
+    let mut conversion = target.conversion() ;
+    let mut items = vec![] ;
+    for (card, stock) in collection.stocks()
+    {
+        for (variant, count) in stock.iter()
+        {
+            let price = prices.price(card, variant).filter(|price| target.learn(&mut conversion, price.currency)) ;
+            items.push(Valued { card: card.clone(), variant: *variant, count, price }) ;
+        }
+    }
+    Valuation { items, conversion }
+}
+
+/// Value of a collection in a currency, see [`value`]<br/>
+/// The value of each currency is summed before being converted, and unpriced copies are left out of every total
+#[derive(Debug, Clone)]
+pub struct Valuation<'gc>
+{
+    pub items: Vec<Valued<'gc>>,
+    /// Currency of the totals, and exchange rates used to convert to it
+    pub conversion: Conversion,
+}
+
+impl<'gc> Valuation<'gc>
+{
+    pub fn total(&self) -> Amount
+    {
+        self.conversion.total(self.items.iter().filter_map(Valued::value))
+    }
+
+    /// Number of copies without price
+    pub fn unpriced(&self) -> usize
+    {
+        self.items.iter().filter(|item| item.price.is_none()).map(|item| item.count).sum()
+    }
+
+    /// Value of each set, in collection order (`None` for cards without serial)
+    pub fn by_set(&self) -> Vec<(Option<String>, Amount)>
+    {
+        self.group(|item| item.card.printing().set())
+    }
+
+    /// Value of each rarity, from commons to mythics
+    pub fn by_rarity(&self) -> Vec<(Rarity, Amount)>
+    {
+        let mut rarities = self.group(|item| *item.card.rarity()) ;
+        rarities.sort_by_key(|(rarity, _)| *rarity as u8) ;
+        rarities
+    }
+
+    /// Value of each finish, from nonfoil to etched
+    pub fn by_finish(&self) -> Vec<(Finish, Amount)>
+    {
+        let mut finishes = self.group(|item| item.variant.finish) ;
+        finishes.sort_by_key(|(finish, _)| *finish) ;
+        finishes
+    }
+
+    /// Value of each condition, from near mint to damaged
+    pub fn by_condition(&self) -> Vec<(Condition, Amount)>
+    {
+        let mut conditions = self.group(|item| item.variant.condition) ;
+        conditions.sort_by_key(|(condition, _)| *condition) ;
+        conditions
+    }
+
+    /// The `n` most valuable cards and variants, by converted price of a copy (in collection order for the same price)
+    pub fn top(&self, n: usize) -> Vec<&Valued<'gc>>
+    {
+        let mut items: Vec<&Valued<'gc>> = self.items.iter().filter(|item| item.price.is_some()).collect() ;
+        items.sort_by_key(|item| std::cmp::Reverse(item.price.and_then(|price| self.conversion.convert(price)))) ;
+        items.truncate(n) ;
+        items
+    }
+
+    /// Sums the values of priced items by key, keys in order of appearance
+    fn group<K: PartialEq>(&self, key: impl Fn(&Valued<'_>) -> K) -> Vec<(K, Amount)>
+    {
+        let mut groups: Vec<(K, Vec<Money>)> = vec![] ;
+        for item in &self.items
+        {
+            let Some(value) = item.value() else { continue } ;
+            let key = key(item) ;
+            match groups.iter_mut().find(|(known, _)| *known == key)
+            {
+                Some((_, values)) => values.push(value),
+                None => groups.push((key, vec![value])),
+            }
+        }
+        groups.into_iter().map(|(key, values)| (key, self.conversion.total(values))).collect()
+    }
+}
+
+/// The total and its currency, the unpriced copies, then the exchange rates used
+/// ```text
+/// Total: 12.50 USD, 3 copies unpriced
+/// Rate: 1 EUR = 1.0842 USD (2026-10-01)
+/// ```
+impl std::fmt::Display for Valuation<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "Total: {}", Money::new(self.total(), self.conversion.currency))? ;
+        match self.unpriced()
+        {
+            0 => writeln!(f)?,
+            1 => writeln!(f, ", 1 copy unpriced")?,
+            unpriced => writeln!(f, ", {} copies unpriced", unpriced)?,
+        }
+        write!(f, "{}", self.conversion)
+    }
+}
+
+#[test]
+fn price_db_valuation()
+{
+    use crate::exchange::ExchangeRates ;
+
+    let catalog = crate::collections::test_catalog() ;
+    let (eur, usd) = (Currency::try_from("EUR").unwrap(), Currency::try_from("USD").unwrap()) ;
+
+    let snapshot = "Date;Serial;Finish;Price;Currency\n\
+                    2026-09-01,LTR C 0001,,0.20,eur\n\
+                    2026-10-01,LTR-1,,\"0,25\",eur\n\
+                    2026-10-01,LTR C 0001,foil,1.00,eur\n\
+                    2026-10-01,LTR R 0004,,2.00,eur\n\
+                    2026-10-01,LTC R 0001,etched,5,eur\n" ;
+    assert_eq!(PriceDb::from_csv(snapshot).unwrap_err(), ImportError::MissingColumn("Serial")) ;
+    let snapshot = snapshot.replacen(';', ",", 4) ;
+    let prices = PriceDb::from_csv(&snapshot).unwrap()
+        .with_multiplier(Condition::LightlyPlayed, 80)
+        .with_multiplier(Condition::Damaged, 25) ;
+    assert_eq!(prices.len(), 5) ;
+    assert_eq!(prices.latest("LTR C 0001", Finish::Nonfoil).map(|quote| (quote.price, quote.currency)), Some((Amount::from_cents(25), eur))) ;
+    assert_eq!(prices.latest("LTR C 0001", Finish::Etched), None) ;
+    assert_eq!(prices.dates(), vec![Timestamp::from_date(2026, 9, 1).unwrap(), Timestamp::from_date(2026, 10, 1).unwrap()]) ;
+    assert_eq!(prices.quote_at("LTR C 0001", Finish::Nonfoil, Timestamp::from_date(2026, 9, 30).unwrap()).map(|quote| quote.price), Some(Amount::from_cents(20))) ;
+    assert_eq!(prices.quote_at("LTR C 0001", Finish::Nonfoil, Timestamp::from_date(2026, 8, 31).unwrap()), None) ;
+
+    let mut csv = vec![] ;
+    prices.write_csv(&mut csv).unwrap() ;
+    let csv = String::from_utf8(csv).unwrap() ;
+    assert!(csv.starts_with("Serial,Finish,Price,Currency,Date\nLTR C 0001,nonfoil,0.20,EUR,2026-09-01\n")) ;
+    assert_eq!(PriceDb::from_csv(&csv).unwrap().quotes().collect::<Vec<_>>(), prices.quotes().collect::<Vec<_>>()) ;
+
+    assert_eq!(PriceDb::from_csv("serial,price,currency,date\nLTR C 0001,free,EUR,2026-10-01\n").unwrap_err(), ImportError::Malformed { line: 2, reason: "Could not parse number" }) ;
+    assert_eq!(PriceDb::from_csv("serial,price,currency,date\n,1,EUR,2026-10-01\n").unwrap_err(), ImportError::Malformed { line: 2, reason: "No serial given" }) ;
+    assert_eq!(PriceDb::from_csv("serial,price,currency,date\nLTR C 0001,1,,2026-10-01\n").unwrap_err(), ImportError::Malformed { line: 2, reason: "Expected a 3-letter currency code" }) ;
+    assert_eq!(PriceDb::from_csv("serial,price,date\nLTR C 0001,1,2026-10-01\n").unwrap_err(), ImportError::MissingColumn("Currency")) ;
+    assert_eq!(PriceDb::from_csv("serial,price,currency\nLTR C 0001,1,EUR\n").unwrap_err(), ImportError::MissingColumn("Date")) ;
+
+    let foil = Variant { finish: Finish::Foil, ..Variant::default() } ;
+    let played = Variant { condition: Condition::LightlyPlayed, ..Variant::default() } ;
+    let damaged = Variant { finish: Finish::Etched, condition: Condition::Damaged, ..Variant::default() } ;
+    let mut collection = Collection::from_counts(&catalog, [("LTR C 0001", 4), ("LTR R 0004", 1), ("Homemade card", 3)]).unwrap() ;
+    collection.add_variant("LTR C 0001", foil, 2).unwrap() ;
+    collection.add_variant("LTR R 0004", played, 1).unwrap() ;
+    collection.add_variant("LTC R 0001", damaged, 1).unwrap() ;
+    collection.add_variant("LTC R 0001", Variant::default(), 1).unwrap() ;
+
+    let rates = ExchangeRates::from_csv("date,from,to,rate\n2026-09-01,EUR,USD,1.10\n").unwrap() ;
+    let valuation = value(&collection, &prices, rates.to(eur, Timestamp::now())) ;
+    // 4 × 0.25 + 2 × 1.00 + 2.00 + 2.00 × 80% + 5.00 × 25%
+    assert_eq!(valuation.total(), Amount::from_cents(785)) ;
+    assert_eq!(valuation.unpriced(), 4) ;
+    assert_eq!(valuation.by_set(), vec![(Some("LTR".to_string()), Amount::from_cents(660)), (Some("LTC".to_string()), Amount::from_cents(125))]) ;
+    assert_eq!(valuation.by_rarity(), vec![(Rarity::Common, Amount::from_cents(300)), (Rarity::Rare, Amount::from_cents(485))]) ;
+    assert_eq!(valuation.by_finish(), vec![
+        (Finish::Nonfoil, Amount::from_cents(460)),
+        (Finish::Foil, Amount::from_cents(200)),
+        (Finish::Etched, Amount::from_cents(125)),
+    ]) ;
+    assert_eq!(valuation.by_condition(), vec![
+        (Condition::NearMint, Amount::from_cents(500)),
+        (Condition::LightlyPlayed, Amount::from_cents(160)),
+        (Condition::Damaged, Amount::from_cents(125)),
+    ]) ;
+    assert_eq!(valuation.top(3).iter().map(|item| (item.card.printing().serial(), item.price.map(|price| price.amount))).collect::<Vec<_>>(), vec![
+        (Some("LTR R 0004"), Some(Amount::from_cents(200))),
+        (Some("LTR R 0004"), Some(Amount::from_cents(160))),
+        (Some("LTC R 0001"), Some(Amount::from_cents(125))),
+    ]) ;
+    assert_eq!(valuation.to_string(), "Total: 7.85 EUR, 4 copies unpriced\n") ;
+
+    // The total is converted at once: 7.85 EUR is 8.635 USD
+    let converted = value(&collection, &prices, rates.to(usd, Timestamp::from_date(2026, 10, 15).unwrap())) ;
+    assert_eq!(converted.total(), Amount::try_from("8.635").unwrap()) ;
+    assert_eq!(converted.to_string(), "Total: 8.64 USD, 4 copies unpriced\nRate: 1 EUR = 1.1 USD (2026-09-01)\n") ;
+    let gbp = value(&collection, &prices, rates.to(Currency::try_from("GBP").unwrap(), Timestamp::now())) ;
+    assert_eq!((gbp.total(), gbp.unpriced(), gbp.conversion.rates.len()), (Amount::default(), 13, 0)) ;
+
+    // Prices in several currencies are never added together without a rate
+    let mut mixed = prices.clone() ;
+    mixed.merge(PriceDb::from_csv("serial,price,currency,date\nLTR C 0001,10,GBP,2026-10-02\n").unwrap()) ;
+    let valuation = value(&collection, &mixed, rates.to(eur, Timestamp::now())) ;
+    assert_eq!((valuation.total(), valuation.unpriced()), (Amount::from_cents(685), 8)) ;
+}
@@ -0,0 +1,220 @@
+use crate::ids::Id;
+
+/// A card reference, as found in decklists and exports, along with its quantity<br/>
+/// Recognized notations are:
+/// <ul>
+/// <li>`"Banish from Edoras"`</li>
+/// <li>`"Banish from Edoras (LTR) 1"`</li>
+/// <li>`"4x Boromir, Warden of the Tower [LTR]"`</li>
+/// <li>`"4 Boromir, Warden of the Tower [LTR] 4"`</li>
+/// <li>`"LTR-0004"`</li>
+/// <li>`"2 LTR C 0001"`</li>
+/// </ul>
+/// ```
+/// use mtg_multitool::ids::Id;
+/// use mtg_multitool::references::Reference;
+///
+/// let reference = Reference::try_from("4x Banish from Edoras (LTR) 1").unwrap() ;
+/// assert_eq!(reference.quantity, 4) ;
+/// assert_eq!(reference.id(), Id::Both("Banish from Edoras", "LTR C 0001")) ;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference<'a>
+{
+    /// Number of copies, `1` if none was given
+    pub quantity: usize,
+
+    name: Option<&'a str>,
+
+    set: Option<&'a str>,
+
+    number: Option<usize>,
+
+    /// Serial built from the set code and collector number, or given as is
+    serial: Option<String>,
+}
+
+impl<'a> Reference<'a>
+{
+    pub fn name(&self) -> Option<&'a str>
+    {
+        self.name
+    }
+
+    /// Set code, such as `"LTR"`
+    pub fn set(&self) -> Option<&'a str>
+    {
+        self.set
+    }
+
+    /// Collector number within the set
+    pub fn number(&self) -> Option<usize>
+    {
+        self.number
+    }
+
+    /// The id this reference points to: a name, a serial, or both
+    pub fn id(&self) -> Id<'_>
+    {
+        match (self.name, self.serial.as_deref())
+        {
+            (Some(name), Some(serial)) => Id::Both(name, serial),
+            (Some(name), None) => Id::Name(name),
+            (None, Some(serial)) => Id::Serial(serial),
+            (None, None) => unreachable!("References are built with a name or a serial"),
+        }
+    }
+
+    /// The serial part of this reference, if the set and collector number are known
+    pub fn serial(&self) -> Option<&str>
+    {
+        self.serial.as_deref()
+    }
+}
+
+impl<'r> From<&'r Reference<'_>> for Id<'r>
+{
+    fn from(value: &'r Reference<'_>) -> Self
+    {
+        value.id()
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Reference<'a>
+{
+    type Error = &'static str;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error>
+    {
+        let (quantity, rest) = split_quantity(value.trim()) ;
+        if rest.is_empty() { return Err("No card given") ; }
+
+        // Serial notations: `LTR C 0001` or `LTR-0004`
+        if crate::ids::is_serial(rest).is_some() || is_dashed_serial(rest)
+        {
+            let (set, rarity, number) = crate::ids::parse_serial(rest).ok_or("Could not parse serial")? ;
+            let set_end = rest.find([' ', '-']).unwrap_or(rest.len()) ;
+            let serial = match rarity
+            {
+                Some(_) => rest.to_string(),
+                None => format!("{} {:04}", set, number),
+            } ;
+
+            return Ok(Reference { quantity, name: None, set: Some(&rest[..set_end]), number: Some(number), serial: Some(serial) }) ;
+        }
+
+        // Name, followed by an optional `(SET)` or `[SET]` and collector number
+        let (name, set, number) = split_set(rest)? ;
+        if name.is_empty() { return Err("No card name given") ; }
+
+        let serial = match (set, number)
+        {
+            (Some(set), Some(number)) => Some(format!("{} {:04}", set.to_ascii_uppercase(), number)),
+            _ => None,
+        } ;
+
+        Ok(Reference { quantity, name: Some(name), set, number, serial })
+    }
+}
+
+/// Splits `"4x Name"`, `"4 x Name"` or `"4 Name"` into `(4, "Name")`; the quantity defaults to 1
+fn split_quantity(s: &str) -> (usize, &str)
+{
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()) ;
+    if digits == 0 { return (1, s) ; }
+
+    let after_digits = &s[digits..] ;
+    let rest = match after_digits.trim_start().strip_prefix(['x', 'X'])
+    {
+        Some(after_x) if after_x.is_empty() || after_x.starts_with(char::is_whitespace) => after_x,
+        _ if after_digits.is_empty() || after_digits.starts_with(char::is_whitespace) => after_digits,
+        _ => return (1, s),
+    } ;
+
+    match s[..digits].parse()
+    {
+        Ok(quantity) => (quantity, rest.trim()),
+        Err(_) => (1, s),
+    }
+}
+
+/// Checks for `SET-NUMBER` serials, such as `"LTR-0004"`
+fn is_dashed_serial(s: &str) -> bool
+{
+    match s.split_once('-')
+    {
+        Some((set, number)) => crate::ids::is_series(set) && !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+/// Splits `"Name (SET) 12"` into `("Name", Some("SET"), Some(12))`
+fn split_set(s: &str) -> Result<(&str, Option<&str>, Option<usize>), &'static str>
+{
+    for (open, close) in [('(', ')'), ('[', ']')]
+    {
+        if let Some(start) = s.rfind(open)
+        {
+            let end = s[start..].find(close).map(|end| start + end).ok_or("Unclosed set code")? ;
+            let set = s[start + 1..end].trim() ;
+            if !crate::ids::is_series(set) { continue ; }
+
+            let after = s[end + 1..].trim() ;
+            let number = if after.is_empty()
+            {
+                None
+            } else {
+                // Suffixed collector numbers (`"3a"`) are other cards than the plain number, which serials cannot tell apart
+                if !after.chars().all(|c| c.is_ascii_digit()) { return Err("Could not parse collector number") ; }
+                Some(after.parse::<usize>().map_err(|_| "Could not parse collector number")?)
+            } ;
+
+            return Ok((s[..start].trim(), Some(set), number)) ;
+        }
+    }
+
+    Ok((s, None, None))
+}
+
+#[test]
+fn reference_try_from_str()
+{
+    use crate::ids::Id::{*} ;
+
+    let r = Reference::try_from("Banish from Edoras (LTR) 1").unwrap() ;
+    assert_eq!((r.quantity, r.name, r.set, r.number), (1, Some("Banish from Edoras"), Some("LTR"), Some(1))) ;
+    assert!(matches!(r.id(), Both("Banish from Edoras", "LTR 0001"))) ;
+    assert_eq!(r.id(), Serial("LTR C 0001")) ;
+
+    let r = Reference::try_from("4x Boromir, Warden of the Tower [LTR]").unwrap() ;
+    assert_eq!((r.quantity, r.name, r.set, r.number), (4, Some("Boromir, Warden of the Tower"), Some("LTR"), None)) ;
+    assert!(matches!(r.id(), Name("Boromir, Warden of the Tower"))) ;
+
+    let r = Reference::try_from("LTR-0004").unwrap() ;
+    assert_eq!((r.quantity, r.name, r.set, r.number), (1, None, Some("LTR"), Some(4))) ;
+    assert_eq!(r.id(), Serial("LTR R 0004")) ;
+
+    let r = Reference::try_from("  2 LTR C 0001 ").unwrap() ;
+    assert_eq!((r.quantity, r.set, r.number), (2, Some("LTR"), Some(1))) ;
+    assert!(matches!(r.id(), Serial("LTR C 0001"))) ;
+
+    let r = Reference::try_from("3 x Dawn of a New Age").unwrap() ;
+    assert_eq!((r.quantity, r.name), (3, Some("Dawn of a New Age"))) ;
+    let r = Reference::try_from("1 Xenagos, the Reveler").unwrap() ;
+    assert_eq!((r.quantity, r.name), (1, Some("Xenagos, the Reveler"))) ;
+    let r = Reference::try_from("2ED Lightning Bolt").unwrap() ;
+    assert_eq!((r.quantity, r.name), (1, Some("2ED Lightning Bolt"))) ;
+    let r = Reference::try_from("12 Bill the Poney (ltr) 3").unwrap() ;
+    assert_eq!((r.quantity, r.name, r.set, r.number), (12, Some("Bill the Poney"), Some("ltr"), Some(3))) ;
+    assert_eq!(r.serial(), Some("LTR 0003")) ;
+    assert_eq!(Reference::try_from("12 Bill the Poney (ltr) 3a"), Err("Could not parse collector number")) ;
+
+    // Parentheses which are not set codes belong to the name
+    let r = Reference::try_from("Our Market Research Shows That Players Like Really Long Card Names (So Make This Card Name Long)").unwrap() ;
+    assert_eq!(r.set, None) ;
+
+    assert!(Reference::try_from("").is_err()) ;
+    assert!(Reference::try_from("4x ").is_err()) ;
+    assert!(Reference::try_from("(LTR) 1").is_err()) ;
+    assert!(Reference::try_from("Banish from Edoras (LTR").is_err()) ;
+}
@@ -0,0 +1,473 @@
+use serde::de::{Error, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::cards::{GenericCard, OwnedCard};
+use crate::ids::{Id, OwnedId};
+use crate::{Classification, Condition, Finish, Frame, Language, ManaCost, Rarity};
+
+/// Serialized as its letter: `"C"`
+impl Serialize for Rarity
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_char(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rarity
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let s = String::deserialize(deserializer)? ;
+        Rarity::try_from(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized with mana symbols: `"{2}{W}"`
+impl Serialize for ManaCost
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ManaCost
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let s = String::deserialize(deserializer)? ;
+        ManaCost::try_from(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as written on the card: `"Legendary Creature - Human Soldier"`
+impl Serialize for Classification
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Classification
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let s = String::deserialize(deserializer)? ;
+        Classification::try_from(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as its code: `"fr"`
+impl Serialize for Language
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let s = String::deserialize(deserializer)? ;
+        Language::try_from(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as its code: `"borderless"`
+impl Serialize for Frame
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Frame
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let s = String::deserialize(deserializer)? ;
+        Frame::try_from(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as its code: `"foil"`
+impl Serialize for Finish
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Finish
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let s = String::deserialize(deserializer)? ;
+        Finish::try_from(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as its code: `"NM"`
+impl Serialize for Condition
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Condition
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let s = String::deserialize(deserializer)? ;
+        Condition::try_from(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+/// `Name` and `Serial` are serialized as plain strings (`"LTR C 0001"`),
+/// `Both` as `{"name": ..., "serial": ...}` and `Either` as `{"name": ..., "serial": ..., "either": true}`
+impl Serialize for Id<'_>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        match self
+        {
+            Id::Name(s) | Id::Serial(s) => serializer.serialize_str(s),
+            Id::Both(name, serial) | Id::Either(name, serial) => {
+                let either = matches!(self, Id::Either(..)) ;
+                let mut map = serializer.serialize_map(Some(if either { 3 } else { 2 }))? ;
+                map.serialize_entry("name", name)? ;
+                map.serialize_entry("serial", serial)? ;
+                if either
+                {
+                    map.serialize_entry("either", &true)? ;
+                }
+                map.end()
+            },
+        }
+    }
+}
+
+/// Plain strings are read as [`Id::from`] would<br/>
+/// Borrows its strings from the input: use [`OwnedId`] for inputs with escaped strings or read from a reader
+impl<'de: 'a, 'a> Deserialize<'de> for Id<'a>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        struct IdVisitor ;
+
+        impl<'de> Visitor<'de> for IdVisitor
+        {
+            type Value = Id<'de> ;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+            {
+                write!(f, "a card name, a serial, or a map with a name and a serial")
+            }
+
+            fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E>
+            {
+                Ok(Id::from(v))
+            }
+
+            fn visit_str<E: Error>(self, _: &str) -> Result<Self::Value, E>
+            {
+                Err(E::custom(NOT_BORROWED))
+            }
+
+            fn visit_string<E: Error>(self, _: String) -> Result<Self::Value, E>
+            {
+                Err(E::custom(NOT_BORROWED))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error>
+            {
+                let (name, serial, either) = read_id_map::<A, &'de str>(map)? ;
+                Ok(match (name, serial)
+                {
+                    (Some(name), Some(serial)) if either => Id::Either(name, serial),
+                    (Some(name), Some(serial)) => Id::Both(name, serial),
+                    (Some(name), None) => Id::Name(name),
+                    (None, Some(serial)) => Id::Serial(serial),
+                    (None, None) => return Err(A::Error::missing_field("name")),
+                })
+            }
+        }
+
+        deserializer.deserialize_any(IdVisitor)
+    }
+}
+
+const NOT_BORROWED: &str = "this string cannot be borrowed from the input, read an OwnedId instead" ;
+
+/// Reads the `name`, `serial` and `either` entries of an id written as a map
+fn read_id_map<'de, A: MapAccess<'de>, S: Deserialize<'de>>(mut map: A) -> Result<(Option<S>, Option<S>, bool), A::Error>
+{
+    let (mut name, mut serial, mut either) = (None, None, false) ;
+    while let Some(key) = map.next_key::<String>()?
+    {
+        match key.as_str()
+        {
+            "name" => name = Some(map.next_value::<S>()?),
+            "serial" => serial = Some(map.next_value::<S>()?),
+            "either" => either = map.next_value::<bool>()?,
+            other => return Err(A::Error::unknown_field(other, &["name", "serial", "either"])),
+        }
+    }
+    Ok((name, serial, either))
+}
+
+/// Serialized the same way as [`Id`]
+impl Serialize for OwnedId
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        Id::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedId
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        struct OwnedIdVisitor ;
+
+        impl<'de> Visitor<'de> for OwnedIdVisitor
+        {
+            type Value = OwnedId ;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+            {
+                write!(f, "a card name, a serial, or a map with a name and a serial")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E>
+            {
+                Ok(Id::from(v).to_owned_id())
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error>
+            {
+                let (name, serial, either) = read_id_map::<A, String>(map)? ;
+                Ok(match (name, serial)
+                {
+                    (Some(name), Some(serial)) if either => OwnedId::Either(name, serial),
+                    (Some(name), Some(serial)) => OwnedId::Both(name, serial),
+                    (Some(name), None) => OwnedId::Name(name),
+                    (None, Some(serial)) => OwnedId::Serial(serial),
+                    (None, None) => return Err(A::Error::missing_field("name")),
+                })
+            }
+        }
+
+        deserializer.deserialize_any(OwnedIdVisitor)
+    }
+}
+
+/// Shared representation of borrowed and owned cards
+#[derive(Serialize, Deserialize)]
+struct CardRepr<S>
+{
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    serial: Option<S>,
+    name: S,
+
+    cost: ManaCost,
+    kind: Classification,
+    rarity: Rarity,
+
+    effects: S,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    artist: Option<S>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    language: Language,
+    #[serde(default, skip_serializing_if = "is_default")]
+    frame: Frame,
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool
+{
+    *value == T::default()
+}
+
+impl<'a> CardRepr<&'a str>
+{
+    fn of(card: &GenericCard<'a>) -> CardRepr<&'a str>
+    {
+        CardRepr
+        {
+            serial: card.printing().serial(),
+            name: card.name(),
+            cost: *card.cost(),
+            kind: card.kind().clone(),
+            rarity: *card.rarity(),
+            effects: card.effects(),
+            artist: card.printing().artist(),
+            language: *card.printing().language(),
+            frame: *card.printing().frame(),
+        }
+    }
+
+    fn build(self) -> Result<GenericCard<'a>, crate::cards::CardError>
+    {
+        let mut builder = GenericCard::builder()
+            .name(self.name)
+            .cost(self.cost)
+            .kind(self.kind)
+            .rarity(self.rarity)
+            .effects(self.effects)
+            .language(self.language)
+            .frame(self.frame) ;
+
+        if let Some(serial) = self.serial
+        {
+            builder = builder.serial(serial) ;
+        }
+        if let Some(artist) = self.artist
+        {
+            builder = builder.artist(artist) ;
+        }
+
+        builder.build()
+    }
+}
+
+/// Serialized as a map: `{"serial": "LTR C 0001", "name": ..., "cost": "{4}{W}", "kind": "Sorcery", "rarity": "C", "effects": ...}`
+impl Serialize for GenericCard<'_>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        CardRepr::of(self).serialize(serializer)
+    }
+}
+
+/// Borrows its strings from the input: use [`OwnedCard`] for inputs with escaped strings
+impl<'de: 'a, 'a> Deserialize<'de> for GenericCard<'a>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        CardRepr::<&'a str>::deserialize(deserializer)?
+            .build()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Serialized the same way as [`GenericCard`]
+impl Serialize for OwnedCard
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        CardRepr::of(&self.as_generic()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedCard
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let repr = CardRepr::<String>::deserialize(deserializer)? ;
+        let borrowed = CardRepr
+        {
+            serial: repr.serial.as_deref(),
+            name: repr.name.as_str(),
+            cost: repr.cost,
+            kind: repr.kind,
+            rarity: repr.rarity,
+            effects: repr.effects.as_str(),
+            artist: repr.artist.as_deref(),
+            language: repr.language,
+            frame: repr.frame,
+        } ;
+
+        borrowed.build()
+            .map(|card| card.to_owned_card())
+            .map_err(D::Error::custom)
+    }
+}
+
+#[test]
+fn core_types_to_json()
+{
+    use crate::Classification::{*} ;
+
+    assert_eq!(serde_json::to_string(&Rarity::Mythical).unwrap(), r#""M""#) ;
+    assert_eq!(serde_json::from_str::<Rarity>(r#""u""#).unwrap(), Rarity::Uncommon) ;
+    assert!(serde_json::from_str::<Rarity>(r#""Z""#).is_err()) ;
+
+    assert_eq!(serde_json::to_string(&ManaCost::try_from("2 w").unwrap()).unwrap(), r#""{2}{W}""#) ;
+    assert_eq!(serde_json::from_str::<ManaCost>(r#""{1}{W}{W}""#).unwrap().white(), 2) ;
+
+    let kind = Legendary(Box::new(Creature("Human Soldier".to_string()))) ;
+    assert_eq!(serde_json::to_string(&kind).unwrap(), r#""Legendary Creature - Human Soldier""#) ;
+    assert_eq!(serde_json::from_str::<Classification>(r#""Legendary Creature - Human Soldier""#).unwrap(), kind) ;
+
+    assert_eq!(serde_json::to_string(&Language::French).unwrap(), r#""fr""#) ;
+    assert_eq!(serde_json::from_str::<Frame>(r#""showcase""#).unwrap(), Frame::Showcase) ;
+    assert_eq!(serde_json::to_string(&Finish::Etched).unwrap(), r#""etched""#) ;
+    assert_eq!(serde_json::from_str::<Condition>(r#""LP""#).unwrap(), Condition::LightlyPlayed) ;
+}
+
+#[test]
+fn id_to_json()
+{
+    for (id, json) in [
+        (Id::Name("Banish from Edoras"), r#""Banish from Edoras""#),
+        (Id::Serial("LTR C 0001"), r#""LTR C 0001""#),
+        (Id::Both("Banish from Edoras", "LTR C 0001"), r#"{"name":"Banish from Edoras","serial":"LTR C 0001"}"#),
+        (Id::Either("Banish from Edoras", "LTR C 0001"), r#"{"name":"Banish from Edoras","serial":"LTR C 0001","either":true}"#),
+    ]
+    {
+        assert_eq!(serde_json::to_string(&id).unwrap(), json) ;
+        let back: Id = serde_json::from_str(json).unwrap() ;
+        assert_eq!(std::mem::discriminant(&back), std::mem::discriminant(&id)) ;
+        assert_eq!(back, id) ;
+    }
+
+    assert!(serde_json::from_str::<Id>("{}").is_err()) ;
+    assert!(matches!(serde_json::from_str::<Id>(r#"{"serial":"LTR C 0001"}"#).unwrap(), Id::Serial("LTR C 0001"))) ;
+
+    // Escaped strings and readers cannot be borrowed from, but can be owned
+    let escaped = r#"{"name":"\u00c9owyn, Fearless Knight","serial":"LTR R 0207"}"# ;
+    assert!(serde_json::from_str::<Id>(escaped).is_err()) ;
+    assert!(serde_json::from_str::<Id>(r#""\u00c9owyn""#).unwrap_err().to_string().contains("OwnedId")) ;
+    let owned: OwnedId = serde_json::from_str(escaped).unwrap() ;
+    assert_eq!(owned, OwnedId::Both("Éowyn, Fearless Knight".to_string(), "LTR R 0207".to_string())) ;
+    let owned: OwnedId = serde_json::from_reader(r#""LTR C 0001""#.as_bytes()).unwrap() ;
+    assert_eq!(owned, OwnedId::Serial("LTR C 0001".to_string())) ;
+    assert_eq!(serde_json::to_string(&owned).unwrap(), r#""LTR C 0001""#) ;
+}
+
+#[test]
+fn card_to_json()
+{
+    use crate::cards::Card ;
+
+    let card = GenericCard::try_from(r#"LTR R 0004;"Boromir, Warden of the Tower";2 w;Legendary Creature - Human Soldier;R;Vigilence"#).unwrap() ;
+    let json = serde_json::to_string(&card).unwrap() ;
+    assert_eq!(json, r#"{"serial":"LTR R 0004","name":"Boromir, Warden of the Tower","cost":"{2}{W}","kind":"Legendary Creature - Human Soldier","rarity":"R","effects":"Vigilence"}"#) ;
+
+    let borrowed: GenericCard = serde_json::from_str(&json).unwrap() ;
+    assert_eq!(borrowed.id(), card.id()) ;
+    assert_eq!(borrowed.kind(), card.kind()) ;
+
+    let owned: OwnedCard = serde_json::from_str(&json).unwrap() ;
+    assert_eq!(serde_json::to_string(&owned).unwrap(), json) ;
+
+    // Escaped strings cannot be borrowed, but can be owned
+    let escaped = r#"{"name":"Gr\u00edma","cost":"{1}{B}","kind":"Sorcery","rarity":"C","effects":"","language":"de"}"#;
+    assert!(serde_json::from_str::<GenericCard>(escaped).is_err()) ;
+    let owned: OwnedCard = serde_json::from_str(escaped).unwrap() ;
+    assert_eq!(owned.name(), "Gríma") ;
+    assert_eq!(owned.as_generic().printing().language(), &Language::German) ;
+
+    assert!(serde_json::from_str::<OwnedCard>(r#"{"serial":"LTR C 0004","name":"Boromir","cost":"{2}{W}","kind":"Sorcery","rarity":"R","effects":""}"#).is_err()) ;
+}
@@ -0,0 +1,403 @@
+use crate::cards::Printing;
+use crate::{Condition, Finish, Language};
+
+/// What sets copies of the same printing apart: their finish, condition and language<br/>
+/// Copies are in their printing's language unless told otherwise (see [`Variant::of`])
+/// ```
+/// use mtg_multitool::stock::Variant;
+/// use mtg_multitool::{Condition, Finish, Language};
+///
+/// let foil = Variant { finish: Finish::Foil, ..Variant::default() } ;
+/// assert_eq!(foil.condition, Condition::NearMint) ;
+/// assert_eq!(foil.language, Language::English) ;
+/// assert_eq!(foil.to_string(), "foil, NM, en") ;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variant
+{
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub finish: Finish,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub condition: Condition,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub language: Language,
+}
+
+impl Variant
+{
+    pub fn new(finish: Finish, condition: Condition, language: Language) -> Variant
+    {
+        Variant { finish, condition, language }
+    }
+
+    /// Nonfoil, near mint copies of a printing, in the language it was printed in
+    pub fn of(printing: &Printing<'_>) -> Variant
+    {
+        Variant { language: *printing.language(), ..Variant::default() }
+    }
+
+    /// Whether these are nonfoil, near mint copies, whatever their language
+    pub fn is_plain(&self) -> bool
+    {
+        self.finish == Finish::default() && self.condition == Condition::default()
+    }
+}
+
+impl std::fmt::Display for Variant
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}, {}, {}", self.finish.code(), self.condition.code(), self.language.code())
+    }
+}
+
+/// Owned copies of a card, broken down by [`Variant`] and location, along with the card's tags<br/>
+/// Locations are free-form (`"Binder 2, page 5"`, `"Box: LTR bulk"`) and compared once normalized
+/// (see [`crate::names::normalize`]); copies without location are unsorted.
+/// Lots of copies are kept in the order they were first added, and only while copies of them are owned
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stock
+{
+    lots: Vec<Lot>,
+    /// Sorted, without duplicates
+    tags: Vec<String>,
+}
+
+/// Copies of a variant, at a location
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lot
+{
+    pub variant: Variant,
+    pub location: Option<String>,
+    pub count: usize,
+}
+
+impl Lot
+{
+    fn is_at(&self, variant: &Variant, location: Option<&str>) -> bool
+    {
+        self.variant == *variant && same_location(self.location.as_deref(), location)
+    }
+}
+
+/// Locations are compared once normalized, unsorted copies only matching unsorted copies
+pub(crate) fn same_location(a: Option<&str>, b: Option<&str>) -> bool
+{
+    match (a, b)
+    {
+        (Some(a), Some(b)) => crate::names::same_name(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+impl Stock
+{
+    /// Total number of copies, regardless of their variant and location
+    pub fn total(&self) -> usize
+    {
+        self.lots.iter().map(|lot| lot.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.lots.is_empty()
+    }
+
+    /// Number of copies of a variant, wherever they are
+    pub fn get(&self, variant: &Variant) -> usize
+    {
+        self.lots.iter()
+            .filter(|lot| lot.variant == *variant)
+            .map(|lot| lot.count)
+            .sum()
+    }
+
+    /// Number of copies at a location (`None` for unsorted copies), whatever their variant
+    pub fn at(&self, location: Option<&str>) -> usize
+    {
+        self.lots.iter()
+            .filter(|lot| same_location(lot.location.as_deref(), location))
+            .map(|lot| lot.count)
+            .sum()
+    }
+
+    /// Iterates over the owned variants and their counts, wherever the copies are
+    pub fn iter(&self) -> impl Iterator<Item = (&Variant, usize)>
+    {
+        let mut variants: Vec<(&Variant, usize)> = vec![] ;
+        for lot in &self.lots
+        {
+            match variants.iter_mut().find(|(variant, _)| **variant == lot.variant)
+            {
+                Some((_, count)) => *count += lot.count,
+                None => variants.push((&lot.variant, lot.count)),
+            }
+        }
+        variants.into_iter()
+    }
+
+    /// Iterates over the lots of copies, by variant and location
+    pub fn lots(&self) -> impl Iterator<Item = &Lot>
+    {
+        self.lots.iter()
+    }
+
+    /// Adds unsorted copies of a variant, returning its new count
+    pub fn add(&mut self, variant: Variant, quantity: usize) -> usize
+    {
+        self.add_at(variant, None, quantity) ;
+        self.get(&variant)
+    }
+
+    /// Adds copies of a variant at a location, returning the number of copies of that variant at that location
+    pub fn add_at(&mut self, variant: Variant, location: Option<&str>, quantity: usize) -> usize
+    {
+        match self.lots.iter_mut().find(|lot| lot.is_at(&variant, location))
+        {
+            Some(lot) => {
+                lot.count = lot.count.saturating_add(quantity) ;
+                lot.count
+            },
+            None if quantity == 0 => 0,
+            None => {
+                let location = location.map(str::trim).filter(|location| !location.is_empty()).map(str::to_string) ;
+                self.lots.push(Lot { variant, location, count: quantity }) ;
+                quantity
+            },
+        }
+    }
+
+    /// Removes copies of a variant, wherever they are (most recently added lots first), returning its new count<br/>
+    /// Nothing is removed if there are less than `quantity` copies of it
+    pub fn remove(&mut self, variant: &Variant, quantity: usize) -> Option<usize>
+    {
+        let owned = self.get(variant) ;
+        if owned < quantity { return None ; }
+
+        self.take_where(quantity, |lot| lot.variant == *variant) ;
+        Some(owned - quantity)
+    }
+
+    /// Number of copies of a variant at a location (`None` for unsorted copies)
+    pub fn count_at(&self, variant: &Variant, location: Option<&str>) -> usize
+    {
+        self.lots.iter()
+            .filter(|lot| lot.is_at(variant, location))
+            .map(|lot| lot.count)
+            .sum()
+    }
+
+    /// Removes copies of a variant at a location (`None` for unsorted copies), returning the number of copies left there<br/>
+    /// Nothing is removed if there are less than `quantity` copies of it there
+    pub fn remove_at(&mut self, variant: &Variant, location: Option<&str>, quantity: usize) -> Option<usize>
+    {
+        let owned = self.count_at(variant, location) ;
+        if owned < quantity { return None ; }
+
+        self.take_where(quantity, |lot| lot.is_at(variant, location)) ;
+        Some(owned - quantity)
+    }
+
+    /// Removes up to `quantity` copies, whatever their variant and location, and returns the number of copies removed<br/>
+    /// Plain copies go first: unsorted ones before sorted ones, [plain](Variant::is_plain) variants before the others,
+    /// then the most recently added lots
+    /// ```
+    /// use mtg_multitool::stock::{Stock, Variant};
+    /// use mtg_multitool::Finish;
+    ///
+    /// let foil = Variant { finish: Finish::Foil, ..Variant::default() } ;
+    /// let mut stock = Stock::default() ;
+    /// stock.add_at(Variant::default(), Some("Binder 1"), 1) ;
+    /// stock.add(Variant::default(), 1) ;
+    /// stock.add(foil, 1) ;
+    ///
+    /// assert_eq!(stock.take(2), 2) ;
+    /// assert_eq!(stock.at(Some("Binder 1")), 1) ;
+    /// ```
+    pub fn take(&mut self, quantity: usize) -> usize
+    {
+        let mut taken = 0 ;
+        for (sorted, special) in [(false, false), (false, true), (true, false), (true, true)]
+        {
+            taken += self.take_where(quantity - taken, |lot| lot.location.is_some() == sorted && lot.variant.is_plain() != special)
+                .iter()
+                .map(|lot| lot.count)
+                .sum::<usize>() ;
+        }
+        taken
+    }
+
+    /// Removes up to `quantity` copies from the matching lots, most recently added first, and returns the removed copies
+    fn take_where<F: Fn(&Lot) -> bool>(&mut self, quantity: usize, matching: F) -> Vec<Lot>
+    {
+        let mut taken = vec![] ;
+        let mut left = quantity ;
+        for lot in self.lots.iter_mut().rev().filter(|lot| matching(lot))
+        {
+            if left == 0 { break ; }
+            let count = left.min(lot.count) ;
+            lot.count -= count ;
+            left -= count ;
+            taken.push(Lot { count, ..lot.clone() }) ;
+        }
+
+        self.lots.retain(|lot| lot.count > 0) ;
+        taken
+    }
+
+    /// Moves copies from a location to another (`None` for unsorted copies), keeping their variant;
+    /// returns the number of copies now at the destination<br/>
+    /// Nothing is moved if there are less than `quantity` copies at `from`
+    pub fn move_copies(&mut self, from: Option<&str>, to: Option<&str>, quantity: usize) -> Option<usize>
+    {
+        if self.at(from) < quantity { return None ; }
+
+        for lot in self.take_where(quantity, |lot| same_location(lot.location.as_deref(), from))
+        {
+            self.add_at(lot.variant, to, lot.count) ;
+        }
+        Some(self.at(to))
+    }
+
+    /// Adds every copy of another stock, at the same locations, along with its tags
+    pub fn merge(&mut self, other: &Stock)
+    {
+        for lot in &other.lots
+        {
+            self.add_at(lot.variant, lot.location.as_deref(), lot.count) ;
+        }
+        for tag in &other.tags
+        {
+            self.tag(tag) ;
+        }
+    }
+
+    /// Sorted tags of the card, such as `"trade"` or `"proxy"`
+    pub fn tags(&self) -> impl Iterator<Item = &str>
+    {
+        self.tags.iter().map(String::as_str)
+    }
+
+    /// Tags are compared once normalized
+    pub fn has_tag(&self, tag: &str) -> bool
+    {
+        self.tags.iter().any(|known| crate::names::same_name(known, tag))
+    }
+
+    /// Adds a tag, returning `false` if the card already had it (or if it is blank)
+    pub fn tag(&mut self, tag: &str) -> bool
+    {
+        let tag = tag.trim() ;
+        if tag.is_empty() || self.has_tag(tag) { return false ; }
+
+        let position = self.tags.partition_point(|known| known.as_str() < tag) ;
+        self.tags.insert(position, tag.to_string()) ;
+        true
+    }
+
+    /// Removes a tag, returning `false` if the card did not have it
+    pub fn untag(&mut self, tag: &str) -> bool
+    {
+        let before = self.tags.len() ;
+        self.tags.retain(|known| !crate::names::same_name(known, tag)) ;
+        self.tags.len() != before
+    }
+}
+
+/// Copies of the default variant only
+impl From<usize> for Stock
+{
+    fn from(value: usize) -> Self
+    {
+        let mut stock = Stock::default() ;
+        stock.add(Variant::default(), value) ;
+        stock
+    }
+}
+
+impl FromIterator<(Variant, usize)> for Stock
+{
+    fn from_iter<I: IntoIterator<Item = (Variant, usize)>>(iter: I) -> Self
+    {
+        let mut stock = Stock::default() ;
+        for (variant, count) in iter
+        {
+            stock.add(variant, count) ;
+        }
+        stock
+    }
+}
+
+#[test]
+fn stock_operations()
+{
+    let foil = Variant::new(Finish::Foil, Condition::NearMint, Language::English) ;
+    let played = Variant::new(Finish::Nonfoil, Condition::HeavilyPlayed, Language::French) ;
+
+    let mut stock = Stock::from(2) ;
+    assert_eq!(stock.add(foil, 1), 1) ;
+    assert_eq!(stock.add(played, 3), 3) ;
+    assert_eq!(stock.add(foil, 0), 1) ;
+    assert_eq!(stock.total(), 6) ;
+    assert_eq!(stock.get(&Variant::default()), 2) ;
+
+    assert_eq!(stock.remove(&foil, 2), None) ;
+    assert_eq!(stock.remove(&foil, 1), Some(0)) ;
+    assert_eq!(stock.iter().map(|(variant, count)| (*variant, count)).collect::<Vec<_>>(), vec![(Variant::default(), 2), (played, 3)]) ;
+
+    assert_eq!(stock.take(4), 4) ;
+    assert_eq!(stock.iter().map(|(variant, count)| (*variant, count)).collect::<Vec<_>>(), vec![(played, 1)]) ;
+    assert_eq!(stock.take(4), 1) ;
+    assert!(stock.is_empty()) ;
+
+    let mut merged: Stock = [(foil, 1), (played, 1), (foil, 1)].into_iter().collect() ;
+    merged.merge(&Stock::from(1)) ;
+    assert_eq!((merged.get(&foil), merged.get(&played), merged.total()), (2, 1, 4)) ;
+}
+
+#[test]
+fn stock_locations_and_tags()
+{
+    let foil = Variant { finish: Finish::Foil, ..Variant::default() } ;
+
+    let mut stock = Stock::from(3) ;
+    assert_eq!(stock.add_at(foil, Some("Binder 2, page 5"), 2), 2) ;
+    assert_eq!(stock.add_at(Variant::default(), Some("binder 2,  page 5"), 1), 1) ;
+    assert_eq!(stock.add_at(foil, Some("  "), 1), 1) ;
+    assert_eq!((stock.total(), stock.at(None), stock.at(Some("BINDER 2, PAGE 5"))), (7, 4, 3)) ;
+    assert_eq!(stock.iter().map(|(variant, count)| (*variant, count)).collect::<Vec<_>>(), vec![(Variant::default(), 4), (foil, 3)]) ;
+
+    // Moves keep the variants, most recently added lots first
+    assert_eq!(stock.move_copies(Some("Binder 2, page 5"), Some("Box: LTR bulk"), 4), None) ;
+    assert_eq!(stock.move_copies(Some("Binder 2, page 5"), Some("Box: LTR bulk"), 2), Some(2)) ;
+    assert_eq!(stock.at(Some("Binder 2, page 5")), 1) ;
+    assert_eq!(stock.lots().map(|lot| (lot.variant, lot.location.as_deref(), lot.count)).collect::<Vec<_>>(), vec![
+        (Variant::default(), None, 3),
+        (foil, Some("Binder 2, page 5"), 1),
+        (foil, None, 1),
+        (Variant::default(), Some("Box: LTR bulk"), 1),
+        (foil, Some("Box: LTR bulk"), 1),
+    ]) ;
+    assert_eq!(stock.move_copies(Some("Box: LTR bulk"), None, 2), Some(6)) ;
+    assert_eq!(stock.total(), 7) ;
+
+    assert_eq!(stock.count_at(&foil, Some("binder 2, page 5")), 1) ;
+    assert_eq!(stock.remove_at(&foil, Some("Binder 2, page 5"), 2), None) ;
+    assert_eq!(stock.remove_at(&foil, Some("Binder 2, page 5"), 1), Some(0)) ;
+    assert_eq!((stock.total(), stock.get(&foil)), (6, 2)) ;
+
+    assert!(stock.tag("trade")) ;
+    assert!(stock.tag("Keep")) ;
+    assert!(!stock.tag(" TRADE ")) ;
+    assert!(!stock.tag("")) ;
+    assert_eq!(stock.tags().collect::<Vec<_>>(), vec!["Keep", "trade"]) ;
+    assert!(stock.has_tag("keep")) ;
+    assert!(stock.untag("KEEP")) ;
+    assert!(!stock.untag("proxy")) ;
+
+    let mut merged = Stock::default() ;
+    merged.merge(&stock) ;
+    assert_eq!(merged, stock) ;
+}
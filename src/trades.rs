@@ -0,0 +1,365 @@
+use crate::cards::GenericCard;
+use crate::collections::{Collection, CollectionError};
+use crate::decks::Decks;
+use crate::exchange::{Conversion, Target};
+use crate::excess::KeepPolicy;
+use crate::loans::LoanLedger;
+use crate::prices::{no_prices, PriceSource};
+use crate::money::{Amount, Money};
+use crate::stock::Variant;
+use crate::wants::WantList;
+
+/// One side of a trade: a collection, the cards wanted for it, and how many copies of each card to keep
+#[derive(Debug, Clone, Copy)]
+pub struct Party<'p, 'gc>
+{
+    pub collection: &'p Collection<'gc>,
+    pub wants: &'p WantList<'gc>,
+    /// Copies of a card above this number, across its printings, are spare, [`crate::completion::PLAYSET`] by default
+    pub keep: usize,
+}
+
+impl<'p, 'gc> Party<'p, 'gc>
+{
+    pub fn new(collection: &'p Collection<'gc>, wants: &'p WantList<'gc>) -> Party<'p, 'gc>
+    {
+        Party { collection, wants, keep: crate::completion::PLAYSET }
+    }
+
+    pub fn with_keep(mut self, keep: usize) -> Party<'p, 'gc>
+    {
+        self.keep = keep ;
+        self
+    }
+}
+
+/// Copies of a variant of a card changing hands
+#[derive(Debug, Clone)]
+pub struct TradeItem<'gc>
+{
+    pub card: GenericCard<'gc>,
+    pub variant: Variant,
+    pub count: usize,
+    /// Price of one copy in the currency it is quoted in, if known
+    pub price: Option<Money>,
+}
+
+impl TradeItem<'_>
+{
+    /// Price of every copy, if known
+    pub fn value(&self) -> Option<Money>
+    {
+        self.price.map(|price| price * self.count)
+    }
+}
+
+/// A proposed trade, from the point of view of the first party of [`propose`]
+#[derive(Debug, Clone)]
+pub struct Trade<'gc>
+{
+    /// Copies going to the other party, most wanted first
+    pub given: Vec<TradeItem<'gc>>,
+    /// Copies coming from the other party, most wanted first
+    pub received: Vec<TradeItem<'gc>>,
+    /// Currency the values of both sides are compared in, and exchange rates used to convert to it
+    pub conversion: Conversion,
+}
+
+/// Suggests a trade between two parties: the spare copies of each party that the other one wants, most wanted first<br/>
+/// Copies priced above the max price of a want are left out, and copies quoted in a currency without rate to the target one are unpriced.
+/// When every copy of the trade has a price, the most valuable side gives up its least wanted copies
+/// as long as it brings both sides closer in value
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::dates::Timestamp;
+/// use mtg_multitool::exchange::ExchangeRates;
+/// use mtg_multitool::money::Currency;
+/// use mtg_multitool::prices::no_prices;
+/// use mtg_multitool::trades::{propose, Party};
+/// use mtg_multitool::wants::WantList;
+/// use mtg_multitool::ltr;
+///
+/// let rates = ExchangeRates::default() ;
+/// let euros = rates.to(Currency::try_from("EUR").unwrap(), Timestamp::now()) ;
+/// let mine = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 6)]).unwrap() ;
+/// let theirs = Collection::from_counts(&ltr::collection(), [("LTR R 0004", 3)]).unwrap() ;
+/// let mut my_wants = WantList::default() ;
+/// my_wants.add(&ltr::collection(), "LTR R 0004", 1).unwrap() ;
+/// let mut their_wants = WantList::default() ;
+/// their_wants.add(&ltr::collection(), "Banish from Edoras", 4).unwrap() ;
+///
+/// let trade = propose(Party::new(&mine, &my_wants), Party::new(&theirs, &their_wants).with_keep(2), &no_prices, euros) ;
+/// assert_eq!(trade.given.iter().map(|item| (*item.card.name(), item.count)).collect::<Vec<_>>(), vec![("Banish from Edoras", 2)]) ;
+/// assert_eq!(trade.received.iter().map(|item| (*item.card.name(), item.count)).collect::<Vec<_>>(), vec![("Boromir, Warden of the Tower", 1)]) ;
+/// ```
+pub fn propose<'gc, P: PriceSource>(me: Party<'_, 'gc>, them: Party<'_, 'gc>, prices: &P, target: Target<'_>) -> Trade<'gc>
+{
+    let mut conversion = target.conversion() ;
+    let given = offer(&me, &them, prices, target, &mut conversion) ;
+    let received = offer(&them, &me, prices, target, &mut conversion) ;
+    let mut trade = Trade { given, received, conversion } ;
+
+    if trade.is_fully_priced()
+    {
+        loop
+        {
+            let (given, received) = (trade.given_value(), trade.received_value()) ;
+            let heavier = if given > received { &mut trade.given } else { &mut trade.received } ;
+            let Some(last) = heavier.last_mut() else { break } ;
+
+            let price = last.price.and_then(|price| trade.conversion.convert(price)).unwrap_or_default() ;
+            let difference = given.max(received) - given.min(received) ;
+            // Giving up a copy must bring both sides closer
+            if price.is_zero() || price >= difference * 2 { break ; }
+
+            last.count -= 1 ;
+            if last.count == 0 { heavier.pop() ; }
+        }
+    }
+
+    trade
+}
+
+/// Spare copies of `from` wanted by `to`, most wanted first, adding the exchange rates of their prices to the conversion
+fn offer<'gc, P: PriceSource>(from: &Party<'_, 'gc>, to: &Party<'_, 'gc>, prices: &P, target: Target<'_>, conversion: &mut Conversion) -> Vec<TradeItem<'gc>>
+{
+    // Copies of a card beyond `keep` are counted across its printings, see [`crate::excess::find`]
+    let excess = crate::excess::find(from.collection, &Decks::default(), &LoanLedger::default(), &KeepPolicy::keeping(from.keep), &no_prices, target) ;
+    let mut spare: Vec<usize> = from.collection.stocks()
+        .map(|(card, _)| excess.excess.iter().find(|excess| excess.card.same_printing(card)).map_or(0, |excess| excess.count()))
+        .collect() ;
+    // Along with the position of their card in `from`
+    let mut items: Vec<(usize, TradeItem<'gc>)> = vec![] ;
+
+    for missing in to.wants.missing(to.collection)
+    {
+        let mut needed = missing.missing ;
+        for (index, (card, stock)) in from.collection.stocks().enumerate()
+        {
+            for (variant, count) in stock.iter().filter(|(variant, _)| missing.want.accepts(card, variant))
+            {
+                let price = prices.price(card, variant).filter(|price| target.learn(conversion, price.currency)) ;
+                let max = missing.want.max_price.filter(|max| target.learn(conversion, max.currency)) ;
+                if let (Some(price), Some(max)) = (price, max)
+                {
+                    if conversion.convert(price) > conversion.convert(max) { continue ; }
+                }
+
+                let offered = items.iter().position(|(known, item)| *known == index && item.variant == *variant) ;
+                let already = offered.map_or(0, |offered| items[offered].1.count) ;
+                let count = needed.min(spare[index]).min(count.saturating_sub(already)) ;
+                if count == 0 { continue ; }
+
+                needed -= count ;
+                spare[index] -= count ;
+                match offered
+                {
+                    Some(offered) => items[offered].1.count += count,
+                    None => items.push((index, TradeItem { card: card.clone(), variant: *variant, count, price })),
+                }
+            }
+        }
+    }
+
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+impl<'gc> Trade<'gc>
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.given.is_empty() && self.received.is_empty()
+    }
+
+    /// Whether every copy of the trade has a price
+    pub fn is_fully_priced(&self) -> bool
+    {
+        self.given.iter().chain(&self.received).all(|item| item.price.is_some())
+    }
+
+    /// Value of the given copies, unpriced ones excluded
+    pub fn given_value(&self) -> Amount
+    {
+        self.conversion.total(self.given.iter().filter_map(TradeItem::value))
+    }
+
+    /// Value of the received copies, unpriced ones excluded
+    pub fn received_value(&self) -> Amount
+    {
+        self.conversion.total(self.received.iter().filter_map(TradeItem::value))
+    }
+
+    /// Applies an accepted trade to both collections, the first one giving and the other one receiving<br/>
+    /// Received copies are unsorted. If any copy cannot be moved, neither collection is changed
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::dates::Timestamp;
+    /// use mtg_multitool::exchange::ExchangeRates;
+    /// use mtg_multitool::money::Currency;
+    /// use mtg_multitool::prices::no_prices;
+    /// use mtg_multitool::trades::{propose, Party};
+    /// use mtg_multitool::wants::WantList;
+    /// use mtg_multitool::ltr;
+    ///
+    /// let rates = ExchangeRates::default() ;
+    /// let mut mine = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 6)]).unwrap() ;
+    /// let mut theirs = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 0)]).unwrap() ;
+    /// let mut their_wants = WantList::default() ;
+    /// their_wants.add(&ltr::collection(), "Banish from Edoras", 4).unwrap() ;
+    ///
+    /// let trade = propose(Party::new(&mine, &WantList::default()), Party::new(&theirs, &their_wants), &no_prices, rates.to(Currency::try_from("EUR").unwrap(), Timestamp::now())) ;
+    /// trade.apply(&mut mine, &mut theirs).unwrap() ;
+    /// assert_eq!((mine.count("Banish from Edoras"), theirs.count("Banish from Edoras")), (Some(4), Some(2))) ;
+    /// ```
+    pub fn apply(&self, mine: &mut Collection<'gc>, theirs: &mut Collection<'gc>) -> Result<(), CollectionError<'gc>>
+    {
+        let (mut new_mine, mut new_theirs) = (mine.clone(), theirs.clone()) ;
+        move_items(&self.given, &mut new_mine, &mut new_theirs)? ;
+        move_items(&self.received, &mut new_theirs, &mut new_mine)? ;
+
+        *mine = new_mine ;
+        *theirs = new_theirs ;
+        Ok(())
+    }
+}
+
+fn move_items<'gc>(items: &[TradeItem<'gc>], from: &mut Collection<'gc>, to: &mut Collection<'gc>) -> Result<(), CollectionError<'gc>>
+{
+    for item in items
+    {
+        from.remove_variant(item.card.id(), &item.variant, item.count)? ;
+        to.add_variant(item.card.id(), item.variant, item.count)? ;
+    }
+    Ok(())
+}
+
+/// The given and received copies, one per line, followed by their values and the exchange rates used
+/// ```text
+/// Give:
+///   2 Banish from Edoras (LTR C 0001), nonfoil, NM, en at 0.25 EUR
+/// Receive:
+///   1 Boromir, Warden of the Tower (LTR R 0004), foil, NM, en at 1.50 USD
+/// Value: 0.50 EUR given, 1.38 EUR received
+/// Rate: 1 USD = 0.92 EUR (2026-10-01)
+/// ```
+impl std::fmt::Display for Trade<'_>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        for (title, items) in [("Give", &self.given), ("Receive", &self.received)]
+        {
+            writeln!(f, "{}:", title)? ;
+            for item in items
+            {
+                write!(f, "  {} {}, {}", item.count, item.card, item.variant)? ;
+                match item.price
+                {
+                    Some(price) => writeln!(f, " at {}", price)?,
+                    None => writeln!(f, ", unpriced")?,
+                }
+            }
+        }
+
+        let currency = self.conversion.currency ;
+        write!(f, "Value: {} given, {} received", Money::new(self.given_value(), currency), Money::new(self.received_value(), currency))? ;
+        if !self.is_fully_priced()
+        {
+            write!(f, " (some copies are unpriced)")? ;
+        }
+        writeln!(f)? ;
+        write!(f, "{}", self.conversion)
+    }
+}
+
+#[test]
+fn trade_balance()
+{
+    use crate::dates::Timestamp ;
+    use crate::exchange::ExchangeRates ;
+    use crate::money::Currency ;
+    use crate::stock::Stock ;
+    use crate::wants::{Priority, Want} ;
+
+    let catalog = crate::collections::test_catalog() ;
+    let (eur, usd) = (Currency::try_from("EUR").unwrap(), Currency::try_from("USD").unwrap()) ;
+    let rates = ExchangeRates::from_csv("date,from,to,rate\n2026-10-01,USD,EUR,0.75\n").unwrap() ;
+    let euros = rates.to(eur, Timestamp::now()) ;
+    let foil = Variant { finish: crate::Finish::Foil, ..Variant::default() } ;
+
+    let mut mine = Collection::from_counts(&catalog, [("LTR C 0001", 7), ("LTR C 0002", 5)]).unwrap() ;
+    mine.add_variant("LTR C 0001", foil, 2).unwrap() ;
+    let mut theirs = Collection::from_counts(&catalog, [("LTR R 0004", 5)]).unwrap() ;
+
+    let mut my_wants = WantList::default() ;
+    my_wants.add(&catalog, "LTR R 0004", 2).unwrap() ;
+    let mut their_wants = WantList::default() ;
+    their_wants.add(&catalog, "LTR C 0002", 4).unwrap() ;
+    their_wants.push(Want { finish: Some(crate::Finish::Foil), priority: Priority::High, ..Want::new(catalog.resolve("LTR C 0001", catalog.policy()).unwrap().clone(), 3) }) ;
+    // Too expensive for them
+    their_wants.push(Want { max_price: Some(Money::new(Amount::from_cents(10), eur)), ..Want::new(catalog.resolve("LTR C 0001", catalog.policy()).unwrap().clone(), 1) }) ;
+
+    let unpriced = propose(Party::new(&mine, &my_wants), Party::new(&theirs, &their_wants), &no_prices, euros) ;
+    assert_eq!(unpriced.given.iter().map(|item| (*item.card.name(), item.variant.finish, item.count)).collect::<Vec<_>>(), vec![
+        ("Banish from Edoras", crate::Finish::Foil, 2),
+        ("Bill the Poney", crate::Finish::Nonfoil, 1),
+        ("Banish from Edoras", crate::Finish::Nonfoil, 1),
+    ]) ;
+    assert!(!unpriced.is_fully_priced()) ;
+
+    let prices = |card: &GenericCard<'_>, variant: &Variant| match (*card.name(), variant.finish)
+    {
+        ("Banish from Edoras", crate::Finish::Foil) => Some(Money::new(Amount::from_cents(100), eur)),
+        ("Banish from Edoras", _) => Some(Money::new(Amount::from_cents(25), eur)),
+        ("Bill the Poney", _) => Some(Money::new(Amount::from_cents(20), eur)),
+        _ => Some(Money::new(Amount::from_cents(200), usd)),
+    } ;
+    let trade = propose(Party::new(&mine, &my_wants), Party::new(&theirs, &their_wants), &prices, euros) ;
+    // Nonfoil Banish is above their max price. 2 foils (2.00) and 1 Bill (0.20) against 1 Boromir (2.00 USD, that is 1.50):
+    // Bill goes, while giving up a foil would not bring both sides closer
+    assert_eq!(trade.given.iter().map(|item| (*item.card.name(), item.variant.finish, item.count)).collect::<Vec<_>>(), vec![
+        ("Banish from Edoras", crate::Finish::Foil, 2),
+    ]) ;
+    assert_eq!(trade.received.iter().map(|item| item.count).collect::<Vec<_>>(), vec![1]) ;
+    assert_eq!((trade.given_value(), trade.received_value()), (Amount::from_cents(200), Amount::from_cents(150))) ;
+    assert_eq!(
+        trade.to_string(),
+        "Give:\n  2 Banish from Edoras (LTR C 0001), foil, NM, en at 1.00 EUR\n\
+         Receive:\n  1 Boromir, Warden of the Tower (LTR R 0004), nonfoil, NM, en at 2.00 USD\n\
+         Value: 2.00 EUR given, 1.50 EUR received\n\
+         Rate: 1 USD = 0.75 EUR (2026-10-01)\n"
+    ) ;
+
+    // Without a rate to euros, copies quoted in dollars are unpriced
+    let euros_only = ExchangeRates::default() ;
+    let unbalanced = propose(Party::new(&mine, &my_wants), Party::new(&theirs, &their_wants), &prices, euros_only.to(eur, Timestamp::now())) ;
+    assert_eq!(unbalanced.received.iter().map(|item| item.price).collect::<Vec<_>>(), vec![None]) ;
+    assert!(!unbalanced.is_fully_priced() && unbalanced.conversion.rates.is_empty()) ;
+
+    trade.apply(&mut mine, &mut theirs).unwrap() ;
+    assert_eq!(mine.count_variant("LTR C 0001", &foil), Some(0)) ;
+    assert_eq!(theirs.stock("LTR C 0001"), Some(&Stock::from_iter([(foil, 2)]))) ;
+    assert_eq!((mine.count("LTR R 0004"), theirs.count("LTR R 0004")), (Some(1), Some(4))) ;
+
+    // Trades no longer possible leave both collections as they were
+    let (before_mine, before_theirs) = (mine.clone(), theirs.clone()) ;
+    assert!(trade.apply(&mut mine, &mut theirs).is_err()) ;
+    assert!(before_mine.diff(&mine).is_empty() && before_theirs.diff(&theirs).is_empty()) ;
+    assert_eq!(mine.count("LTR R 0004"), Some(1)) ;
+}
+
+#[test]
+fn trade_spare_across_printings()
+{
+    let catalog = crate::collections::test_catalog() ;
+    let mine = Collection::from_counts(&catalog, [("LTR C 0001", 3), ("LTC R 0001", 3)]).unwrap() ;
+    let theirs = Collection::from_counts(&catalog, [("LTR C 0001", 0)]).unwrap() ;
+    let mut their_wants = WantList::default() ;
+    their_wants.add(&catalog, "LTR C 0001", 4).unwrap() ;
+    their_wants.add(&catalog, "LTC R 0001", 4).unwrap() ;
+
+    // 6 Banish across both printings, 4 of them kept
+    let rates = crate::exchange::ExchangeRates::default() ;
+    let euros = rates.to(crate::money::Currency::try_from("EUR").unwrap(), crate::dates::Timestamp::now()) ;
+    let trade = propose(Party::new(&mine, &WantList::default()), Party::new(&theirs, &their_wants), &no_prices, euros) ;
+    assert_eq!(trade.given.iter().map(|item| item.count).sum::<usize>(), 2) ;
+}
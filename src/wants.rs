@@ -0,0 +1,307 @@
+use crate::cards::GenericCard;
+use crate::collections::{Collection, CollectionError};
+use crate::ids::{AsId, Policy};
+use crate::imports::{import, Format, ImportReport};
+use crate::money::Money;
+use crate::stock::Variant;
+use crate::Finish;
+
+/// How much a card is wanted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Priority
+{
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Priority
+{
+    pub fn name(&self) -> &'static str
+    {
+        match self
+        {
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+        }
+    }
+}
+
+/// ```
+/// use mtg_multitool::wants::Priority;
+/// assert_eq!(Priority::try_from("high"), Ok(Priority::High)) ;
+/// assert_eq!(Priority::try_from("Normal"), Ok(Priority::Medium)) ;
+/// assert!(Priority::try_from("asap").is_err()) ;
+/// ```
+impl TryFrom<&str> for Priority
+{
+    type Error = &'static str ;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error>
+    {
+        match value.trim().to_ascii_lowercase().as_str()
+        {
+            "low" => Ok(Priority::Low),
+            "" | "medium" | "normal" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            _ => Err("Expected low, medium or high"),
+        }
+    }
+}
+
+/// Copies of a card someone wants<br/>
+/// When a finish or a set is preferred, only copies of that finish or from that set count toward the want
+#[derive(Debug, Clone)]
+pub struct Want<'gc>
+{
+    /// Any printing of the wanted card
+    pub card: GenericCard<'gc>,
+    pub quantity: usize,
+    pub priority: Priority,
+    /// Most one is willing to pay for a copy
+    pub max_price: Option<Money>,
+    pub finish: Option<Finish>,
+    /// Set code, such as `"LTR"`
+    pub set: Option<String>,
+}
+
+impl<'gc> Want<'gc>
+{
+    /// Wants copies of a card, of any finish and from any set
+    pub fn new(card: GenericCard<'gc>, quantity: usize) -> Want<'gc>
+    {
+        Want { card, quantity, priority: Priority::default(), max_price: None, finish: None, set: None }
+    }
+
+    /// Whether copies of a variant of a card count toward this want
+    pub fn accepts(&self, card: &GenericCard<'_>, variant: &Variant) -> bool
+    {
+        card.oracle().same_card(self.card.oracle())
+            && self.finish.is_none_or(|finish| finish == variant.finish)
+            && self.set.as_deref().is_none_or(|set| card.printing().set().is_some_and(|known| known.eq_ignore_ascii_case(set)))
+    }
+
+    /// Number of copies of a collection counting toward this want
+    pub fn owned(&self, collection: &Collection<'_>) -> usize
+    {
+        collection.stocks()
+            .flat_map(|(card, stock)| stock.iter().filter(move |(variant, _)| self.accepts(card, variant)))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    fn same_want(&self, other: &Want<'_>) -> bool
+    {
+        self.card.oracle().same_card(other.card.oracle())
+            && self.finish == other.finish
+            && self.set.as_deref().map(str::to_ascii_uppercase) == other.set.as_deref().map(str::to_ascii_uppercase)
+    }
+}
+
+/// Cards someone wants, in the order they were added
+/// ```
+/// use mtg_multitool::collections::Collection;
+/// use mtg_multitool::wants::{Priority, WantList};
+/// use mtg_multitool::{ltr, Finish};
+///
+/// let mut wants = WantList::default() ;
+/// wants.add(&ltr::collection(), "Banish from Edoras", 4).unwrap() ;
+/// let boromir = wants.add(&ltr::collection(), "LTR R 0004", 1).unwrap() ;
+/// boromir.priority = Priority::High ;
+/// boromir.finish = Some(Finish::Foil) ;
+///
+/// let collection = Collection::from_counts(&ltr::collection(), [("Banish from Edoras", 3), ("LTR R 0004", 1)]).unwrap() ;
+/// let missing = wants.missing(&collection) ;
+/// assert_eq!(missing.iter().map(|missing| (*missing.want.card.name(), missing.owned, missing.missing)).collect::<Vec<_>>(), vec![
+///     ("Boromir, Warden of the Tower", 0, 1),
+///     ("Banish from Edoras", 3, 1),
+/// ]) ;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WantList<'gc>
+{
+    wants: Vec<Want<'gc>>,
+}
+
+impl<'gc> WantList<'gc>
+{
+    /// Wants copies of a card of a catalog (such as `ltr::collection()`), and returns the want so that preferences can be set<br/>
+    /// Copies are added to the want of the same card without preferences, if any
+    pub fn add<'any, T>(&mut self, catalog: &Collection<'gc>, id: T, quantity: usize) -> Result<&mut Want<'gc>, CollectionError<'gc>>
+        where T: AsId<'any>
+    {
+        let card = catalog.resolve(id, catalog.policy())?.clone() ;
+        Ok(self.push(Want::new(card, quantity)))
+    }
+
+    /// Adds a want, merging it with the want of the same card with the same preferences, if any
+    pub fn push(&mut self, want: Want<'gc>) -> &mut Want<'gc>
+    {
+        match self.wants.iter().position(|known| known.same_want(&want))
+        {
+            Some(position) => {
+                let known = &mut self.wants[position] ;
+                known.quantity = known.quantity.saturating_add(want.quantity) ;
+                known
+            },
+            None => {
+                self.wants.push(want) ;
+                self.wants.last_mut().expect("A want was just pushed")
+            },
+        }
+    }
+
+    /// Wants every card of a decklist, in MTG Arena text (see [`Format::Arena`]), any printing of them being fine<br/>
+    /// Lines giving only the name of a reprinted card resolve to its newest printing (see [`Policy::PreferNewest`]).
+    /// Lines which do not resolve to a card of the catalog are left out, and listed in the report
+    /// ```
+    /// use mtg_multitool::collections::Collection;
+    /// use mtg_multitool::wants::WantList;
+    /// use mtg_multitool::ltr;
+    ///
+    /// let deck = "Deck\n4 Banish from Edoras (LTR) 1\n2 Boromir, Warden of the Tower\n\nSideboard\n1 Banish from Edoras\n1 Sauron's Ring\n" ;
+    /// let (wants, report) = WantList::from_decklist(&ltr::collection(), deck) ;
+    /// assert_eq!(wants.iter().map(|want| (*want.card.name(), want.quantity)).collect::<Vec<_>>(), vec![
+    ///     ("Banish from Edoras", 5),
+    ///     ("Boromir, Warden of the Tower", 2),
+    /// ]) ;
+    /// assert_eq!(report.unmatched.len(), 1) ;
+    /// ```
+    pub fn from_decklist(catalog: &Collection<'gc>, text: &str) -> (WantList<'gc>, ImportReport<'gc>)
+    {
+        let catalog = catalog.clone().with_policy(Policy::PreferNewest) ;
+        let (deck, report) = import(&catalog, Format::Arena, text).expect("Arena text is read line by line, without failing") ;
+        let mut wants = WantList::default() ;
+        for (card, count) in deck.owned()
+        {
+            wants.push(Want::new(card.clone(), count)) ;
+        }
+        (wants, report)
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.wants.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.wants.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Want<'gc>>
+    {
+        self.wants.iter()
+    }
+
+    /// Stops wanting a card, whatever the preferences; returns `false` if it was not wanted
+    pub fn remove(&mut self, card: &GenericCard<'_>) -> bool
+    {
+        let before = self.wants.len() ;
+        self.wants.retain(|want| !want.card.oracle().same_card(card.oracle())) ;
+        self.wants.len() != before
+    }
+
+    /// Wants which a collection does not fulfill, highest priority first (in list order within a priority)
+    pub fn missing<'w>(&'w self, collection: &Collection<'_>) -> Vec<Missing<'w, 'gc>>
+    {
+        let mut missing: Vec<Missing<'w, 'gc>> = self.wants.iter()
+            .map(|want| {
+                let owned = want.owned(collection) ;
+                Missing { want, owned, missing: want.quantity.saturating_sub(owned) }
+            })
+            .filter(|missing| missing.missing > 0)
+            .collect() ;
+        missing.sort_by_key(|missing| std::cmp::Reverse(missing.want.priority)) ;
+        missing
+    }
+}
+
+impl<'w, 'gc> IntoIterator for &'w WantList<'gc>
+{
+    type Item = &'w Want<'gc> ;
+    type IntoIter = std::slice::Iter<'w, Want<'gc>> ;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        self.iter()
+    }
+}
+
+/// A want a collection does not fulfill, see [`WantList::missing`]
+#[derive(Debug, Clone)]
+pub struct Missing<'w, 'gc>
+{
+    pub want: &'w Want<'gc>,
+    /// Copies counting toward the want
+    pub owned: usize,
+    pub missing: usize,
+}
+
+impl Missing<'_, '_>
+{
+    /// Most one is willing to pay for the missing copies, if the want has a max price
+    pub fn budget(&self) -> Option<Money>
+    {
+        self.want.max_price.map(|price| price * self.missing)
+    }
+}
+
+#[test]
+fn want_list_missing()
+{
+    use crate::money::{Amount, Currency} ;
+
+    let catalog = crate::collections::test_catalog() ;
+    let eur = Currency::try_from("EUR").unwrap() ;
+    let mut collection = Collection::from_counts(&catalog, [("LTR C 0001", 2), ("LTC R 0001", 1), ("LTR R 0004", 3)]).unwrap() ;
+    let foil = Variant { finish: Finish::Foil, ..Variant::default() } ;
+    collection.add_variant("LTR C 0001", foil, 1).unwrap() ;
+
+    let banish = catalog.resolve("LTR C 0001", catalog.policy()).unwrap().clone() ;
+    let mut wants = WantList::default() ;
+    wants.add(&catalog, "LTR C 0001", 4).unwrap() ;
+    wants.push(Want { set: Some("ltc".to_string()), max_price: Some(Money::new(Amount::from_cents(150), eur)), ..Want::new(banish.clone(), 2) }) ;
+    let foils = wants.push(Want { finish: Some(Finish::Foil), ..Want::new(banish.clone(), 2) }) ;
+    foils.priority = Priority::High ;
+    wants.add(&catalog, "LTR R 0004", 2).unwrap() ;
+    // Copies of the same card without preferences add up, whatever the printing given
+    wants.add(&catalog, "LTC R 0001", 1).unwrap() ;
+    assert!(wants.add(&catalog, "Sauron", 1).is_err()) ;
+
+    assert_eq!(wants.iter().map(|want| (want.quantity, want.set.as_deref(), want.finish)).collect::<Vec<_>>(), vec![
+        (5, None, None),
+        (2, Some("ltc"), None),
+        (2, None, Some(Finish::Foil)),
+        (2, None, None),
+    ]) ;
+
+    let missing = wants.missing(&collection) ;
+    assert_eq!(missing.iter().map(|missing| (missing.want.priority, missing.owned, missing.missing)).collect::<Vec<_>>(), vec![
+        (Priority::High, 1, 1),
+        (Priority::Medium, 4, 1),
+        (Priority::Medium, 1, 1),
+    ]) ;
+    assert_eq!(missing[2].budget(), Some(Money::new(Amount::from_cents(150), eur))) ;
+    assert_eq!(missing[1].budget(), None) ;
+
+    assert!(wants.remove(&banish)) ;
+    assert_eq!(wants.len(), 1) ;
+    assert!(wants.missing(&collection).is_empty()) ;
+}
+
+#[test]
+fn want_list_from_decklist()
+{
+    let catalog = crate::collections::test_catalog() ;
+
+    // Banish from Edoras was printed in LTR then LTC, either being fine
+    let (wants, report) = WantList::from_decklist(&catalog, "Deck\n3 Banish from Edoras\n1 Banish from Edoras (LTR) 1\n2 Bill the Poney\n") ;
+    assert!(report.is_complete()) ;
+    assert_eq!(wants.iter().map(|want| (*want.card.name(), want.quantity, want.set.as_deref())).collect::<Vec<_>>(), vec![
+        ("Banish from Edoras", 4, None),
+        ("Bill the Poney", 2, None),
+    ]) ;
+}